@@ -0,0 +1,82 @@
+//! Where the CLI keeps its files, following each platform's conventions unless
+//! overridden.
+//!
+//! For defaults, see https://docs.rs/directories/4.0.1/directories/struct.ProjectDirs.html#examples
+
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context, Result};
+use directories::ProjectDirs;
+
+use lib::archive::Archive;
+use lib::config::{self, Config};
+use lib::spot::{Spot, Spots};
+
+pub struct Dirs {
+    project: ProjectDirs,
+    config: Config,
+    spots_path: Option<PathBuf>,
+    archive_path: Option<PathBuf>,
+}
+
+impl Dirs {
+    pub fn new() -> Result<Self> {
+        let project = config::project_dirs().ok_or_else(|| {
+            anyhow!("Couldn't find an appropriate cache dir, please specify with --spots-path")
+        })?;
+        Ok(Self {
+            project,
+            config: Config::default(),
+            spots_path: None,
+            archive_path: None,
+        })
+    }
+
+    /// Use the config's spots file, and resolve its favorite aliases
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Override the spots file location
+    pub fn spots_path(mut self, path: Option<PathBuf>) -> Self {
+        self.spots_path = path;
+        self
+    }
+
+    /// Override the forecast archive location
+    pub fn archive_path(mut self, path: Option<PathBuf>) -> Self {
+        self.archive_path = path;
+        self
+    }
+
+    /// The spots file, written by `update`
+    pub fn spots_file(&self) -> PathBuf {
+        self.spots_path
+            .clone()
+            .or_else(|| self.config.spots_path.clone())
+            .unwrap_or_else(|| self.project.cache_dir().join("spots.json"))
+    }
+
+    /// The directory of archived forecasts
+    pub fn archive_dir(&self) -> PathBuf {
+        self.archive_path
+            .clone()
+            .unwrap_or_else(|| self.project.data_dir().join("archive"))
+    }
+
+    pub fn spots(&self) -> Result<Spots> {
+        Spots::from_path(self.spots_file()).context("No spots found, try running `update`")
+    }
+
+    /// Find a spot by name, or by the alias of a favorite
+    pub fn find_spot(&self, name: &str) -> Result<Spot> {
+        self.spots()?
+            .find(self.config.resolve(name))
+            .ok_or_else(|| anyhow!("Unknown spot {name:?}, try running `update`"))
+    }
+
+    pub fn archive(&self) -> Result<Archive> {
+        Archive::open(self.archive_dir())
+    }
+}
@@ -1,58 +1,496 @@
-use anyhow::{anyhow, bail, Result};
-use clap::Parser;
-use directories::ProjectDirs;
+use anyhow::{anyhow, Result};
+use chrono::{NaiveDate, Utc};
+use clap::{CommandFactory, Parser, Subcommand};
 use std::{
+    env,
     fs::{self, File},
+    future::Future,
     path::PathBuf,
 };
 
+use lib::api;
+use lib::archive::Run;
+use lib::config::{self, Config};
+use lib::diff::RunDiff;
 use lib::msw::crawler::Crawler;
+use lib::msw::forecast::Forecast;
+use lib::ndbc::BuoyAPI;
+use lib::provider;
+use lib::rating::Rater;
+use lib::spot::Spot;
+use lib::ui::{
+    self, Ascii, ColorDepth, Comparison, Favorites, Layout, Plain, Report, Terminal, Theme, Tides,
+};
+use lib::units::Units;
+
+mod dirs;
+
+use dirs::Dirs;
 
 /// The accompanying CLI to thesurf.in
+///
+/// Without a command, summarizes the favorite spots in the config file
 #[derive(Parser, Debug)]
-#[clap(name = "thesurf.in", bin_name = "thesurf.in", version, author, version, about, long_about = None)]
+#[clap(name = "thesurf.in", bin_name = "thesurf.in", version, author, about)]
 struct Args {
-    /// Surf spot
-    #[clap(required_unless_present("update"))]
-    spot: Option<String>,
+    /// Filepath of the config file
+    #[clap(long, global = true, value_hint = clap::ValueHint::FilePath)]
+    config: Option<PathBuf>,
+
+    /// Filepath of the spot mapping json
+    #[clap(long, global = true, value_hint = clap::ValueHint::FilePath)]
+    spots_path: Option<PathBuf>,
+
+    /// Directory of the forecast archive
+    #[clap(long, global = true, value_hint = clap::ValueHint::DirPath)]
+    archive_path: Option<PathBuf>,
+
+    /// Print without colors. Also disabled by the config, or by setting
+    /// NO_COLOR
+    #[clap(long, global = true)]
+    no_color: bool,
+
+    /// Print with ASCII characters only. Implies --no-color
+    #[clap(long, global = true)]
+    ascii: bool,
+
+    /// Width to lay out views for, in columns. Defaults to COLUMNS, or the
+    /// terminal's width
+    #[clap(long, global = true)]
+    width: Option<usize>,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Show the forecast for a spot
+    Forecast {
+        /// Surf spot
+        spot: String,
+
+        /// Units to show the forecast in, e.g. `eu`, `us`, or `m,kts`
+        #[clap(long)]
+        units: Option<Units>,
+
+        /// Print the forecast as JSON
+        #[clap(long)]
+        json: bool,
+
+        /// Where to get the forecast from [default: open-meteo]
+        #[clap(long, possible_values = provider::PROVIDERS)]
+        provider: Option<String>,
+    },
+
+    /// Compare the forecasts for several spots
+    Compare {
+        /// Surf spots
+        #[clap(required = true, min_values = 2)]
+        spots: Vec<String>,
+
+        /// Units to show the forecasts in, e.g. `eu`, `us`, or `m,kts`
+        #[clap(long)]
+        units: Option<Units>,
+
+        /// Print the forecasts as JSON
+        #[clap(long)]
+        json: bool,
+
+        /// Where to get the forecasts from [default: open-meteo]
+        #[clap(long, possible_values = provider::PROVIDERS)]
+        provider: Option<String>,
+    },
+
+    /// List or search the available surf spots
+    Spots {
+        #[clap(subcommand)]
+        command: SpotsCommand,
+    },
+
+    /// Update MSW surf spot mapping, with coordinates from Open-Meteo geocoding
+    Update,
+
+    /// Run the web server
+    Serve {
+        #[clap(long, default_value = "127.0.0.1")]
+        host: String,
+
+        #[clap(short, long, default_value_t = 8080)]
+        port: u16,
+
+        /// Where to get forecasts from [default: open-meteo]
+        #[clap(long, possible_values = provider::PROVIDERS)]
+        provider: Option<String>,
+    },
+
+    /// Show recent observations from an NDBC buoy
+    Buoy {
+        /// NDBC station ID, e.g. 41009
+        station: String,
+
+        /// Print the observations as JSON
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Show high and low tides for a spot, in local time
+    Tide {
+        /// Surf spot
+        spot: String,
+
+        /// Number of days to show
+        #[clap(short, long, default_value_t = 3)]
+        days: u32,
+    },
+
+    /// Show a past forecast from the archive
+    History {
+        /// Surf spot
+        spot: String,
+
+        /// Show the forecast as of this date (UTC), e.g. 2024-07-04
+        ///
+        /// Defaults to the latest archived forecast
+        #[clap(short, long)]
+        date: Option<NaiveDate>,
+    },
+
+    /// Show what changed between the two latest archived model runs
+    Diff {
+        /// Surf spot
+        spot: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum SpotsCommand {
+    /// List all spots
+    List {
+        /// Print the spots as JSON
+        #[clap(long)]
+        json: bool,
+    },
 
-    /// Update MSW surf spot mapping
-    #[clap(short, long)]
-    update: bool,
+    /// Find spots whose name contains the given text
+    Search {
+        substring: String,
 
-    /// Filepath to put spot mapping json
-    ///
-    /// For defaults, see https://docs.rs/directories/4.0.1/directories/struct.ProjectDirs.html#examples
-    #[clap(short, long, requires = "update", value_hint = clap::ValueHint::FilePath)]
-    path: Option<String>,
+        /// Print the spots as JSON
+        #[clap(long)]
+        json: bool,
+    },
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    let config = match &args.config {
+        Some(path) => Config::from_path(path)?,
+        None => Config::load()?,
+    };
+    let out = Output {
+        ascii: args.ascii,
+        color: !args.no_color
+            && config
+                .color
+                .unwrap_or_else(|| env::var_os("NO_COLOR").is_none()),
+        colors: config.colors.unwrap_or_else(ColorDepth::from_env),
+        theme: config.theme.unwrap_or_default(),
+        width: args.width.unwrap_or_else(terminal_width),
+    };
+    let units = config.units;
+    let default_provider = config
+        .provider
+        .clone()
+        .unwrap_or_else(|| provider::DEFAULT.to_owned());
+    let dirs = Dirs::new()?
+        .config(config.clone())
+        .spots_path(args.spots_path)
+        .archive_path(args.archive_path);
 
-    if args.update {
-        let file_path = match args.path {
-            Some(fp) => PathBuf::from(fp),
-            None => {
-                let project_dirs =
-                    ProjectDirs::from("", "Sam Tay", "thesurf.in").ok_or_else(|| {
-                        anyhow!(
-                            "Couldn't find an appropriate cache dir, please specify with --path"
-                        )
-                    })?;
-                let cache_dir = project_dirs.cache_dir();
-                fs::create_dir_all(cache_dir)?;
-                cache_dir.join("spots.json")
+    let command = match args.command {
+        Some(command) => command,
+        None => return favorites(&dirs, &config, out, &default_provider),
+    };
+    match command {
+        Command::Forecast {
+            spot,
+            units: units_arg,
+            json,
+            provider,
+        } => forecast(
+            &dirs,
+            out,
+            &spot,
+            units_arg.or(units),
+            json,
+            &provider.unwrap_or(default_provider),
+        ),
+        Command::Compare {
+            spots,
+            units: units_arg,
+            json,
+            provider,
+        } => compare(
+            &dirs,
+            out,
+            &spots,
+            units_arg.or(units),
+            json,
+            &provider.unwrap_or(default_provider),
+        ),
+        Command::Spots { command } => {
+            let (search, json) = match &command {
+                SpotsCommand::List { json } => (None, *json),
+                SpotsCommand::Search { substring, json } => (Some(substring.as_str()), *json),
+            };
+            let found = dirs.spots()?.search(search);
+            if json {
+                out.json(api::spots(found))
+            } else {
+                let spot_list = found
+                    .into_iter()
+                    .map(|spot| (spot.slug, spot.id))
+                    .collect::<Vec<_>>();
+                out.print(spot_list);
+                Ok(())
             }
-        };
-        let mut file = File::create(file_path)?;
-        Crawler::new().crawl_spot_ids(&mut file)?;
+        }
+        Command::Update => {
+            let file_path = dirs.spots_file();
+            if let Some(dir) = file_path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            let mut file = File::create(file_path)?;
+            Crawler::new().crawl_spot_ids(&mut file)
+        }
+        Command::Serve {
+            host,
+            port,
+            provider,
+        } => server::Server::new()
+            .config(config)
+            .host(host)
+            .port(port)
+            .spots_path(dirs.spots_file())
+            .archive_path(dirs.archive_dir())
+            .provider(provider.unwrap_or(default_provider))
+            .run(),
+        Command::Buoy { station, json } => {
+            let buoy = block_on(BuoyAPI::new().get(&station))??
+                .ok_or_else(|| anyhow!("No buoy found for station {station:?}"))?;
+            if json {
+                out.json(api::buoy(buoy))
+            } else {
+                out.print(buoy);
+                Ok(())
+            }
+        }
+        Command::Tide { spot, days } => {
+            let spot = dirs.find_spot(&spot)?;
+            let station = spot
+                .tides()
+                .ok_or_else(|| anyhow!("No tide station known for {}", spot.slug))?;
+            let tides = Tides::new(station, Utc::now().timestamp(), days);
+            match spot.zone() {
+                Some(zone) => out.print(tides.zone(zone)),
+                None => out.print(tides),
+            }
+            Ok(())
+        }
+        Command::History { spot, date } => {
+            let spot = dirs.find_spot(&spot)?;
+            let archive = dirs.archive()?;
+            let run = match date {
+                Some(date) => archive.as_of(&spot.slug, date)?,
+                None => archive.latest(&spot.slug, 1)?.into_iter().next(),
+            }
+            .ok_or_else(|| anyhow!("No archived forecast for {}", spot.slug))?;
+            out.print(Report::for_spot(convert(run.forecast, units), &spot));
+            Ok(())
+        }
+        Command::Diff { spot } => {
+            let spot = dirs.find_spot(&spot)?;
+            let (after, before) = dirs
+                .archive()?
+                .latest_pair(&spot.slug)?
+                .ok_or_else(|| anyhow!("No newer model run for {} to compare", spot.slug))?;
+            let convert = |run: Run| Run {
+                forecast: convert(run.forecast, units),
+                ..run
+            };
+            out.print(RunDiff::new(&convert(before), &convert(after)));
+            Ok(())
+        }
     }
+}
 
-    if let Some(_spot) = args.spot {
-        dbg!(_spot);
-        bail!("Not yet implemented")
+fn forecast(
+    dirs: &Dirs,
+    out: Output,
+    spot_name: &str,
+    units: Option<Units>,
+    json: bool,
+    provider: &str,
+) -> Result<()> {
+    let spot = dirs.find_spot(spot_name)?;
+    let provider = provider::from_name(provider)?;
+    let forecast = block_on(provider.forecast(&spot))??;
+    archive(dirs, &spot, &forecast);
+    let forecast = convert(forecast, units);
+    if json {
+        let rater = Rater::for_spot(&spot).range(&forecast);
+        return out.json(api::forecast(Some(spot), forecast, Some(&rater)));
     }
+    out.print(Report::for_spot(forecast, &spot));
+    Ok(())
+}
 
+fn compare(
+    dirs: &Dirs,
+    out: Output,
+    spot_names: &[String],
+    units: Option<Units>,
+    json: bool,
+    provider: &str,
+) -> Result<()> {
+    let provider = provider::from_name(provider)?;
+    let (forecasts, errors) = block_on(async {
+        let mut forecasts = Vec::with_capacity(spot_names.len());
+        let mut errors = Vec::new();
+        for name in spot_names {
+            let forecast = match dirs.find_spot(name) {
+                Ok(spot) => provider.forecast(&spot).await.map(|fc| (spot, fc)),
+                Err(e) => Err(e),
+            };
+            match forecast {
+                Ok((spot, forecast)) => {
+                    archive(dirs, &spot, &forecast);
+                    forecasts.push((spot, convert(forecast, units)));
+                }
+                Err(e) => errors.push((name, e)),
+            }
+        }
+        (forecasts, errors)
+    })?;
+    if json {
+        for (name, e) in errors {
+            eprintln!("Couldn't forecast {name}: {e:#}");
+        }
+        return out.json(api::compare(forecasts));
+    }
+    let comparison = forecasts
+        .into_iter()
+        .fold(Comparison::new(), |view, (spot, forecast)| {
+            view.spot(spot, forecast)
+        });
+    let comparison = errors.into_iter().fold(comparison, |view, (name, e)| {
+        view.error(name, format!("{e:#}"))
+    });
+    out.print(comparison);
     Ok(())
 }
+
+/// Keep a copy for `history` and `diff`, but don't fail over it
+fn archive(dirs: &Dirs, spot: &Spot, forecast: &[Forecast]) {
+    if let Ok(archive) = dirs.archive() {
+        let _ = archive.store(spot, forecast);
+    }
+}
+
+fn convert(forecast: Vec<Forecast>, units: Option<Units>) -> Vec<Forecast> {
+    match units {
+        Some(units) => forecast.into_iter().map(|fc| fc.in_units(&units)).collect(),
+        None => forecast,
+    }
+}
+
+/// Summarize the favorite spots, or explain how to add some
+fn favorites(dirs: &Dirs, config: &Config, out: Output, provider: &str) -> Result<()> {
+    if config.favorites.is_empty() {
+        Args::command().print_help()?;
+        let path = Config::default_path().unwrap_or_else(|| PathBuf::from(config::CONFIG_FILE));
+        println!(
+            "\nAdd favorite spots to {} to see a summary of them here, e.g.\n",
+            path.display()
+        );
+        println!("[[favorites]]\nspot = \"ormond-beach\"\nalias = \"home\"");
+        return Ok(());
+    }
+
+    let provider = provider::from_name(provider)?;
+    let summary = block_on(async {
+        let mut summary = Favorites::new(Utc::now().timestamp());
+        for favorite in &config.favorites {
+            let spot = match dirs.find_spot(&favorite.spot) {
+                Ok(spot) => spot,
+                Err(e) => {
+                    summary = summary.error(favorite.label(), e);
+                    continue;
+                }
+            };
+            summary = match provider.forecast(&spot).await {
+                Ok(forecast) => {
+                    archive(dirs, &spot, &forecast);
+                    summary.spot(favorite.label(), spot, convert(forecast, config.units))
+                }
+                Err(e) => summary.error(favorite.label(), e),
+            };
+        }
+        summary
+    })?;
+    out.print(summary);
+    Ok(())
+}
+
+/// How to print results
+#[derive(Clone, Copy)]
+struct Output {
+    ascii: bool,
+    color: bool,
+    colors: ColorDepth,
+    theme: Theme,
+    width: usize,
+}
+
+impl Output {
+    fn print(self, view: impl Layout) {
+        if self.ascii {
+            print!("{}", ui::render_width(Ascii, view, self.width));
+        } else if self.color {
+            print!(
+                "{}",
+                ui::render_width(
+                    Terminal::new(self.colors).theme(self.theme),
+                    view,
+                    self.width
+                )
+            );
+        } else {
+            print!("{}", ui::render_width(Plain, view, self.width));
+        }
+    }
+
+    fn json(self, body: impl serde::Serialize) -> Result<()> {
+        println!("{}", serde_json::to_string_pretty(&body)?);
+        Ok(())
+    }
+}
+
+/// The width of the terminal, per COLUMNS or else as queried, falling back to
+/// the default when output isn't a terminal
+fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .or_else(|| terminal_size::terminal_size().map(|(width, _)| width.0 as usize))
+        .unwrap_or(ui::DEFAULT_WIDTH)
+}
+
+/// Run a future to completion on a new single threaded runtime
+fn block_on<F: Future>(future: F) -> Result<F::Output> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    Ok(runtime.block_on(future))
+}
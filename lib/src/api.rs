@@ -0,0 +1,203 @@
+//! Versioned JSON representations of forecasts, spots and buoys, for scripting
+//! against the server. See `docs/api.md` for the schema.
+//!
+//! Any breaking change to the shape of these responses, or of the types they
+//! contain, must bump [`VERSION`].
+
+use serde::Serialize;
+
+use crate::diff::RunDiff;
+use crate::msw::forecast::Forecast;
+use crate::ndbc::Buoy;
+use crate::rating::{Rater, Rating};
+use crate::spot::Spot;
+
+/// Current version of the JSON API
+pub const VERSION: u32 = 1;
+
+/// Every response carries the API version alongside its body
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response<T> {
+    pub version: u32,
+    #[serde(flatten)]
+    pub body: T,
+}
+
+impl<T> Response<T> {
+    fn new(body: T) -> Self {
+        Self {
+            version: VERSION,
+            body,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForecastBody {
+    /// The spot forecasted, if known
+    pub spot: Option<Spot>,
+    pub forecast: Vec<RatedForecast>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RatedForecast {
+    #[serde(flatten)]
+    pub forecast: Forecast,
+    /// Our own rating, when a rater is given
+    pub rating: Option<Rating>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareBody {
+    pub spots: Vec<ForecastBody>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotsBody {
+    pub spots: Vec<Spot>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuoyBody {
+    pub buoy: Buoy,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffBody {
+    pub spot: Spot,
+    pub diff: RunDiff,
+}
+
+/// Represent a forecast, optionally rating each entry
+pub fn forecast(
+    spot: Option<Spot>,
+    forecast: Vec<Forecast>,
+    rater: Option<&Rater>,
+) -> Response<ForecastBody> {
+    let forecast = forecast
+        .into_iter()
+        .map(|fc| RatedForecast {
+            rating: rater.map(|rater| rater.rate(&fc)),
+            forecast: fc,
+        })
+        .collect();
+    Response::new(ForecastBody { spot, forecast })
+}
+
+/// Represent several spots' forecasts, each rated against its own profile
+pub fn compare(spots: Vec<(Spot, Vec<Forecast>)>) -> Response<CompareBody> {
+    let spots = spots
+        .into_iter()
+        .map(|(spot, fc)| {
+            let rater = Rater::for_spot(&spot).range(&fc);
+            forecast(Some(spot), fc, Some(&rater)).body
+        })
+        .collect();
+    Response::new(CompareBody { spots })
+}
+
+pub fn spots(spots: Vec<Spot>) -> Response<SpotsBody> {
+    Response::new(SpotsBody { spots })
+}
+
+pub fn buoy(buoy: Buoy) -> Response<BuoyBody> {
+    Response::new(BuoyBody { buoy })
+}
+
+pub fn diff(spot: Spot, diff: RunDiff) -> Response<DiffBody> {
+    Response::new(DiffBody { spot, diff })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::ndbc::parse_observations;
+
+    fn recorded() -> Vec<Forecast> {
+        serde_json::from_str(include_str!("../../test/msw/forecast.json")).unwrap()
+    }
+
+    #[test]
+    fn forecast_schema() {
+        let spot = Spot::new(4203, "ormond-beach").bearing(80.0);
+        let rater = Rater::for_spot(&spot);
+        let json = serde_json::to_value(forecast(Some(spot), recorded(), Some(&rater))).unwrap();
+        assert_eq!(json["version"], VERSION);
+        assert_eq!(json["spot"]["slug"], "ormond-beach");
+        let first = &json["forecast"][0];
+        assert_eq!(first["timestamp"], 1657004400);
+        assert_eq!(first["localTimestamp"], 1656979200);
+        assert_eq!(first["swell"]["unit"], "ft");
+        assert_eq!(
+            first["swell"]["components"]["primary"]["compassDirection"],
+            "W"
+        );
+        assert_eq!(first["wind"]["unit"], "mph");
+        assert_eq!(first["condition"]["unitPressure"], "mb");
+        assert!(first["rating"]["stars"].is_u64());
+        assert!(first["rating"]["level"].is_string());
+        assert!(first["rating"]["reasons"][0]["detail"].is_string());
+    }
+
+    #[test]
+    fn forecast_round_trips() {
+        // Forecasts keep the shape of the MSW API, so we can read our own output
+        let forecast = recorded();
+        let json = serde_json::to_string(&forecast).unwrap();
+        let parsed: Vec<Forecast> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, forecast);
+    }
+
+    #[test]
+    fn unrated_forecast_has_null_rating() {
+        let json = serde_json::to_value(forecast(None, recorded(), None)).unwrap();
+        assert_eq!(json["spot"], Value::Null);
+        assert_eq!(json["forecast"][0]["rating"], Value::Null);
+    }
+
+    #[test]
+    fn compare_schema() {
+        let spots = vec![
+            (Spot::new(4203, "ormond-beach"), recorded()),
+            (Spot::new(4204, "ormond-beach-pier"), recorded()),
+        ];
+        let json = serde_json::to_value(compare(spots)).unwrap();
+        assert_eq!(json["version"], VERSION);
+        assert_eq!(json["spots"][1]["spot"]["slug"], "ormond-beach-pier");
+        assert!(json["spots"][1]["forecast"][0]["rating"]["stars"].is_u64());
+    }
+
+    #[test]
+    fn spots_and_buoy_schema() {
+        let json = serde_json::to_value(spots(vec![Spot::new(162, "mavericks")])).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "version": VERSION,
+                "spots": [{ "id": 162, "slug": "mavericks", "name": "Mavericks" }]
+            })
+        );
+
+        let observations =
+            parse_observations(include_str!("../../test/ndbc/41009.txt"), None).unwrap();
+        let json = serde_json::to_value(buoy(Buoy {
+            station: "41009".to_owned(),
+            observations,
+        }))
+        .unwrap();
+        let latest = &json["buoy"]["observations"][287];
+        assert_eq!(json["buoy"]["station"], "41009");
+        assert_eq!(latest["timestamp"], 1720025400);
+        assert_eq!(latest["windSpeed"], 5.0);
+        assert_eq!(latest["waveHeight"], Value::Null);
+    }
+}
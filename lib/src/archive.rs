@@ -0,0 +1,229 @@
+//! An on-disk archive of every forecast run fetched, so that past forecasts
+//! can be compared against what actually happened.
+//!
+//! Runs are keyed by spot and issue time, and stored as fetched, before any
+//! unit conversion.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{Duration, NaiveDate, Utc};
+
+use crate::msw::forecast::Forecast;
+use crate::spot::Spot;
+
+/// A single forecast run for a spot
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    /// Unix timestamp of when the run was issued
+    pub issued: i64,
+    pub forecast: Vec<Forecast>,
+}
+
+impl Run {
+    /// A run's issue time, falling back to the given time for providers that
+    /// don't report one
+    pub fn new(forecast: Vec<Forecast>, fetched: i64) -> Self {
+        let issued = forecast
+            .iter()
+            .filter_map(|fc| fc.issue_timestamp)
+            .max()
+            .unwrap_or(fetched);
+        Self { issued, forecast }
+    }
+}
+
+/// Forecast runs stored in an embedded database
+#[derive(Clone)]
+pub struct Archive {
+    db: sled::Db,
+}
+
+impl Archive {
+    /// Open the archive at the given directory, creating it if necessary
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let db = sled::open(path)
+            .with_context(|| format!("Failed to open forecast archive at {}", path.display()))?;
+        Ok(Self { db })
+    }
+
+    /// Store a freshly fetched forecast, replacing any run for the spot with
+    /// the same issue time. A forecast identical to the latest run is the same
+    /// model run fetched again, so it isn't stored twice. Returns the stored
+    /// run's issue time.
+    pub fn store(&self, spot: &Spot, forecast: &[Forecast]) -> Result<i64> {
+        self.store_fetched(spot, forecast, Utc::now().timestamp())
+    }
+
+    fn store_fetched(&self, spot: &Spot, forecast: &[Forecast], fetched: i64) -> Result<i64> {
+        if let Some(latest) = self.latest(&spot.slug, 1)?.pop() {
+            if latest.forecast == forecast {
+                return Ok(latest.issued);
+            }
+        }
+        let run = Run::new(forecast.to_vec(), fetched);
+        self.db.insert(
+            key(&spot.slug, run.issued),
+            serde_json::to_vec(&run.forecast)?,
+        )?;
+        Ok(run.issued)
+    }
+
+    /// The latest run issued up to the end of the given date (UTC), i.e. what
+    /// was forecast as of that date
+    pub fn as_of(&self, slug: &str, date: NaiveDate) -> Result<Option<Run>> {
+        let end = (date + Duration::days(1)).and_hms(0, 0, 0).timestamp();
+        self.db
+            .range(key(slug, i64::MIN)..key(slug, end))
+            .next_back()
+            .map(|entry| parse(entry?))
+            .transpose()
+    }
+
+    /// The most recent runs for a spot, newest first
+    pub fn latest(&self, slug: &str, n: usize) -> Result<Vec<Run>> {
+        self.db
+            .scan_prefix(prefix(slug))
+            .rev()
+            .take(n)
+            .map(|entry| parse(entry?))
+            .collect()
+    }
+
+    /// The two most recent distinct runs for a spot, newest first. Older runs
+    /// identical to the newest, i.e. the same model run fetched again, are
+    /// skipped.
+    pub fn latest_pair(&self, slug: &str) -> Result<Option<(Run, Run)>> {
+        let mut runs = self
+            .db
+            .scan_prefix(prefix(slug))
+            .rev()
+            .map(|entry| parse(entry?));
+        let after = match runs.next().transpose()? {
+            Some(run) => run,
+            None => return Ok(None),
+        };
+        for before in runs {
+            let before = before?;
+            if before.forecast != after.forecast {
+                return Ok(Some((after, before)));
+            }
+        }
+        Ok(None)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn temporary() -> Self {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        Self { db }
+    }
+}
+
+/// Slugs never contain a nul byte, so one spot's keys never prefix another's
+fn prefix(slug: &str) -> Vec<u8> {
+    let mut prefix = slug.as_bytes().to_vec();
+    prefix.push(0);
+    prefix
+}
+
+/// Issue times are stored big endian with the sign bit flipped, so keys sort
+/// chronologically
+fn key(slug: &str, issued: i64) -> Vec<u8> {
+    let mut key = prefix(slug);
+    key.extend_from_slice(&((issued as u64) ^ (1 << 63)).to_be_bytes());
+    key
+}
+
+fn parse((key, value): (sled::IVec, sled::IVec)) -> Result<Run> {
+    let ts: [u8; 8] = key[key.len() - 8..].try_into()?;
+    Ok(Run {
+        issued: (u64::from_be_bytes(ts) ^ (1 << 63)) as i64,
+        forecast: serde_json::from_slice(&value)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn forecast(issued: i64) -> Vec<Forecast> {
+        let json = include_str!("../../test/msw/forecast.json");
+        let mut forecast: Vec<Forecast> = serde_json::from_str(json).unwrap();
+        for fc in forecast.iter_mut() {
+            fc.issue_timestamp = Some(issued);
+        }
+        forecast
+    }
+
+    #[test]
+    fn looks_up_runs_by_date() {
+        let archive = Archive::temporary();
+        let spot = Spot::new(4203, "ormond-beach");
+        let other = Spot::new(4204, "ormond-beach-pier");
+        // 2022-07-05 00:00, 06:00, and 2022-07-06 00:00 UTC
+        for issued in [1656979200, 1657000800, 1657065600] {
+            assert_eq!(archive.store(&spot, &forecast(issued)).unwrap(), issued);
+        }
+        archive.store(&other, &forecast(1657022400)).unwrap();
+
+        let date = |d| NaiveDate::from_ymd(2022, 7, d);
+        assert_eq!(archive.as_of("ormond-beach", date(4)).unwrap(), None);
+        let run = archive.as_of("ormond-beach", date(5)).unwrap().unwrap();
+        assert_eq!(run.issued, 1657000800);
+        assert_eq!(run.forecast, forecast(1657000800));
+        let run = archive.as_of("ormond-beach", date(30)).unwrap().unwrap();
+        assert_eq!(run.issued, 1657065600);
+
+        let latest = archive.latest("ormond-beach", 2).unwrap();
+        let issued: Vec<_> = latest.iter().map(|run| run.issued).collect();
+        assert_eq!(issued, vec![1657065600, 1657000800]);
+    }
+
+    #[test]
+    fn stores_refetched_runs_once() {
+        let archive = Archive::temporary();
+        let spot = Spot::new(4203, "ormond-beach");
+        let mut unissued = forecast(0);
+        for fc in unissued.iter_mut() {
+            fc.issue_timestamp = None;
+        }
+        let fetched = 1657000000;
+        assert_eq!(
+            archive.store_fetched(&spot, &unissued, fetched).unwrap(),
+            fetched
+        );
+        let later = archive.store_fetched(&spot, &unissued, fetched + 60);
+        assert_eq!(later.unwrap(), fetched);
+        assert_eq!(archive.latest("ormond-beach", 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn pairs_distinct_runs() {
+        let archive = Archive::temporary();
+        // Stored directly, as if fetched twice before runs were deduplicated
+        let insert = |issued, forecast: &[Forecast]| {
+            let value = serde_json::to_vec(forecast).unwrap();
+            archive
+                .db
+                .insert(key("ormond-beach", issued), value)
+                .unwrap();
+        };
+        insert(1656979200, &forecast(1656979200));
+        insert(1657000800, &forecast(1657000800));
+        assert!(archive.latest_pair("ormond-beach").unwrap().is_some());
+        insert(1657000860, &forecast(1657000800));
+        let (after, before) = archive.latest_pair("ormond-beach").unwrap().unwrap();
+        assert_eq!((after.issued, before.issued), (1657000860, 1656979200));
+        assert_eq!(archive.latest_pair("ormond-beach-pier").unwrap(), None);
+    }
+
+    #[test]
+    fn falls_back_to_fetch_time() {
+        let mut forecast = forecast(0);
+        for fc in forecast.iter_mut() {
+            fc.issue_timestamp = None;
+        }
+        assert_eq!(Run::new(forecast, 1657000000).issued, 1657000000);
+    }
+}
@@ -0,0 +1,389 @@
+//! An in-memory forecast cache, so that upstream providers are only asked for
+//! a forecast when a newer one is likely to have been issued.
+//!
+//! Expired forecasts are still served for a while, with a refresh running in
+//! the background, and concurrent requests for the same forecast share a single
+//! upstream fetch.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use serde::Serialize;
+use tokio::sync::OnceCell;
+
+use crate::archive::Archive;
+use crate::msw::forecast::Forecast;
+use crate::provider::ForecastProvider;
+use crate::spot::Spot;
+use crate::units::Units;
+
+/// Seconds between forecast model runs
+const ISSUE_INTERVAL: i64 = 6 * 60 * 60;
+
+/// Seconds to keep a forecast without an issue time, or whose next issue is
+/// already overdue
+const DEFAULT_TTL: i64 = 60 * 60;
+
+/// Seconds past expiry that a forecast may be served while it's refreshed
+const DEFAULT_MAX_STALE: i64 = 24 * 60 * 60;
+
+/// Provider name and spot slug. Forecasts are kept as fetched, and converted
+/// into the requested units on the way out.
+type Key = (&'static str, String);
+
+/// An upstream fetch, shared by everyone waiting on it
+type Fetch = Arc<OnceCell<Result<Entry, String>>>;
+
+#[derive(Clone)]
+struct Entry {
+    forecast: Arc<Vec<Forecast>>,
+    /// Unix timestamp after which the forecast is stale
+    expires: i64,
+}
+
+#[derive(Default)]
+struct Slot {
+    entry: Option<Entry>,
+    fetch: Option<Fetch>,
+}
+
+/// Counts of how requests were served, for monitoring
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct CacheStats {
+    /// Served a fresh forecast from the cache
+    pub hits: u64,
+    /// Served an expired forecast from the cache, while refreshing it
+    pub stale_hits: u64,
+    /// Had to wait for a new upstream fetch
+    pub misses: u64,
+    /// Had to wait, but joined a fetch already in flight
+    pub coalesced: u64,
+    /// Upstream fetches that failed, including background refreshes
+    pub errors: u64,
+    /// Fetched forecasts that couldn't be archived
+    pub archive_errors: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    hits: AtomicU64,
+    stale_hits: AtomicU64,
+    misses: AtomicU64,
+    coalesced: AtomicU64,
+    errors: AtomicU64,
+    archive_errors: AtomicU64,
+}
+
+impl Counters {
+    fn incr(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Caches forecasts from a provider, keyed by provider and spot.
+///
+/// Forecasts are fresh until the next model run is expected, based on their
+/// issue time, and dropped once they're too stale to serve. Background
+/// refreshes are spawned on the current tokio runtime.
+#[derive(Clone)]
+pub struct ForecastCache {
+    provider: Arc<dyn ForecastProvider>,
+    ttl: i64,
+    max_stale: i64,
+    archive: Option<Archive>,
+    slots: Arc<Mutex<HashMap<Key, Slot>>>,
+    counters: Arc<Counters>,
+}
+
+impl ForecastCache {
+    pub fn new(provider: Arc<dyn ForecastProvider>) -> Self {
+        Self {
+            provider,
+            ttl: DEFAULT_TTL,
+            max_stale: DEFAULT_MAX_STALE,
+            archive: None,
+            slots: Arc::new(Mutex::new(HashMap::new())),
+            counters: Arc::new(Counters::default()),
+        }
+    }
+
+    /// How long to keep forecasts when the next issue time is unknown
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl.as_secs() as i64;
+        self
+    }
+
+    /// How long past expiry a forecast can be served while it's refreshed
+    pub fn max_stale(mut self, max_stale: Duration) -> Self {
+        self.max_stale = max_stale.as_secs() as i64;
+        self
+    }
+
+    /// Archive every forecast fetched from upstream
+    pub fn archive(mut self, archive: Archive) -> Self {
+        self.archive = Some(archive);
+        self
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let get = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        CacheStats {
+            hits: get(&self.counters.hits),
+            stale_hits: get(&self.counters.stale_hits),
+            misses: get(&self.counters.misses),
+            coalesced: get(&self.counters.coalesced),
+            errors: get(&self.counters.errors),
+            archive_errors: get(&self.counters.archive_errors),
+        }
+    }
+
+    /// Gets the forecast for the given spot, converted into the given units
+    pub async fn forecast(&self, spot: &Spot, units: Option<Units>) -> Result<Vec<Forecast>> {
+        let key = (self.provider.name(), spot.slug.clone());
+        let now = Utc::now().timestamp();
+        let fetch = {
+            let mut slots = self.slots.lock().unwrap();
+            let slot = slots.entry(key.clone()).or_default();
+            match &slot.entry {
+                Some(entry) if now < entry.expires => {
+                    Counters::incr(&self.counters.hits);
+                    return Ok(convert(&entry.forecast, units));
+                }
+                Some(entry) if now < entry.expires + self.max_stale => {
+                    Counters::incr(&self.counters.stale_hits);
+                    let forecast = convert(&entry.forecast, units);
+                    if slot.fetch.is_none() {
+                        let fetch = Fetch::default();
+                        slot.fetch = Some(fetch.clone());
+                        let (cache, spot) = (self.clone(), spot.clone());
+                        tokio::spawn(async move {
+                            let _ = cache.fetch(key, &spot, fetch).await;
+                        });
+                    }
+                    return Ok(forecast);
+                }
+                _ => {}
+            }
+            match &slot.fetch {
+                Some(fetch) => {
+                    Counters::incr(&self.counters.coalesced);
+                    fetch.clone()
+                }
+                None => {
+                    Counters::incr(&self.counters.misses);
+                    let fetch = Fetch::default();
+                    slot.fetch = Some(fetch.clone());
+                    fetch
+                }
+            }
+        };
+        self.fetch(key, spot, fetch)
+            .await
+            .map(|entry| convert(&entry.forecast, units))
+            .map_err(|e| anyhow!(e))
+    }
+
+    /// Run or wait on the fetch, then store the result if it succeeded, and
+    /// drop whatever's too stale to serve. If the task running the fetch is
+    /// dropped, the next waiter takes over.
+    async fn fetch(&self, key: Key, spot: &Spot, fetch: Fetch) -> Result<Entry, String> {
+        let result = fetch
+            .get_or_init(|| async {
+                let forecast = self.provider.forecast(spot).await.map_err(|e| {
+                    Counters::incr(&self.counters.errors);
+                    format!("{e:#}")
+                })?;
+                if let Some(archive) = &self.archive {
+                    // Archiving is best effort, and shouldn't fail the request
+                    if archive.store(spot, &forecast).is_err() {
+                        Counters::incr(&self.counters.archive_errors);
+                    }
+                }
+                Ok(Entry {
+                    expires: self.expires(&forecast, Utc::now().timestamp()),
+                    forecast: Arc::new(forecast),
+                })
+            })
+            .await
+            .clone();
+
+        let mut slots = self.slots.lock().unwrap();
+        if let Some(slot) = slots.get_mut(&key) {
+            if slot.fetch.as_ref().is_some_and(|f| Arc::ptr_eq(f, &fetch)) {
+                slot.fetch = None;
+                if let Ok(entry) = &result {
+                    slot.entry = Some(entry.clone());
+                }
+            }
+        }
+        let now = Utc::now().timestamp();
+        let servable = |entry: &Entry| now < entry.expires + self.max_stale;
+        slots.retain(|_, slot| slot.fetch.is_some() || slot.entry.as_ref().is_some_and(servable));
+        result
+    }
+
+    /// A forecast expires when the next model run is due, or after the TTL if
+    /// that isn't known
+    fn expires(&self, forecast: &[Forecast], now: i64) -> i64 {
+        forecast
+            .iter()
+            .filter_map(|fc| fc.issue_timestamp)
+            .max()
+            .map(|issued| issued + ISSUE_INTERVAL)
+            .filter(|next_issue| *next_issue > now)
+            .unwrap_or(now + self.ttl)
+    }
+}
+
+fn convert(forecast: &[Forecast], units: Option<Units>) -> Vec<Forecast> {
+    match units {
+        Some(units) => forecast
+            .iter()
+            .map(|fc| fc.clone().in_units(&units))
+            .collect(),
+        None => forecast.to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::msw::forecast::UnitLength;
+
+    /// Counts upstream fetches, yielding once so that requests can overlap
+    struct Counting {
+        calls: AtomicUsize,
+        forecast: Vec<Forecast>,
+    }
+
+    #[async_trait]
+    impl ForecastProvider for Counting {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        async fn forecast(&self, _spot: &Spot) -> Result<Vec<Forecast>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            Ok(self.forecast.clone())
+        }
+    }
+
+    fn provider() -> Arc<Counting> {
+        let json = include_str!("../../test/msw/forecast.json");
+        Arc::new(Counting {
+            calls: AtomicUsize::new(0),
+            forecast: serde_json::from_str(json).unwrap(),
+        })
+    }
+
+    fn spot() -> Spot {
+        Spot::new(4203, "ormond-beach")
+    }
+
+    #[tokio::test]
+    async fn caches_by_spot_across_units() {
+        let provider = provider();
+        // The recorded forecast was issued long ago, so the TTL applies
+        let cache = ForecastCache::new(provider.clone()).ttl(Duration::from_secs(3600));
+        cache.forecast(&spot(), None).await.unwrap();
+        cache.forecast(&spot(), None).await.unwrap();
+        let eu = Some(Units {
+            length: Some(UnitLength::Meters),
+            ..Units::default()
+        });
+        let converted = cache.forecast(&spot(), eu).await.unwrap();
+        assert_eq!(converted[0].swell.unit, UnitLength::Meters);
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 2,
+                misses: 1,
+                ..CacheStats::default()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn coalesces_concurrent_requests() {
+        let provider = provider();
+        let cache = ForecastCache::new(provider.clone());
+        let spot = spot();
+        let (a, b) = tokio::join!(cache.forecast(&spot, None), cache.forecast(&spot, None));
+        assert_eq!(a.unwrap(), b.unwrap());
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+        let stats = cache.stats();
+        assert_eq!((stats.misses, stats.coalesced), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn serves_stale_while_refreshing() {
+        let provider = provider();
+        let cache = ForecastCache::new(provider.clone()).ttl(Duration::ZERO);
+        let first = cache.forecast(&spot(), None).await.unwrap();
+        let stale = cache.forecast(&spot(), None).await.unwrap();
+        assert_eq!(first, stale);
+        assert_eq!(cache.stats().stale_hits, 1);
+        // Join the background refresh rather than guess when it's done
+        let key = (provider.name(), spot().slug);
+        let refresh = cache.slots.lock().unwrap()[&key].fetch.clone().unwrap();
+        cache.fetch(key.clone(), &spot(), refresh).await.unwrap();
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+        assert!(cache.slots.lock().unwrap()[&key].fetch.is_none());
+
+        // Too stale to serve
+        let cache = ForecastCache::new(provider.clone())
+            .ttl(Duration::ZERO)
+            .max_stale(Duration::ZERO);
+        cache.forecast(&spot(), None).await.unwrap();
+        cache.forecast(&spot(), None).await.unwrap();
+        assert_eq!(cache.stats().misses, 2);
+        // and dropped rather than kept around
+        assert!(cache.slots.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn archives_fetched_forecasts() {
+        let provider = provider();
+        let archive = Archive::temporary();
+        let cache = ForecastCache::new(provider.clone()).archive(archive.clone());
+        let eu = Some(Units::from(crate::msw::forecast::UnitType::Eu));
+        cache.forecast(&spot(), eu).await.unwrap();
+        let runs = archive.latest("ormond-beach", 2).unwrap();
+        assert_eq!(runs.len(), 1);
+        // Stored as fetched
+        assert_eq!(runs[0].forecast, provider.forecast);
+    }
+
+    #[test]
+    fn expires_at_next_issue() {
+        let cache = ForecastCache::new(provider());
+        let mut forecast = provider().forecast.clone();
+        let issued = forecast
+            .iter()
+            .filter_map(|fc| fc.issue_timestamp)
+            .max()
+            .unwrap();
+        assert_eq!(cache.expires(&forecast, issued), issued + ISSUE_INTERVAL);
+        // Overdue
+        let late = issued + ISSUE_INTERVAL;
+        assert_eq!(cache.expires(&forecast, late), late + DEFAULT_TTL);
+        for fc in forecast.iter_mut() {
+            fc.issue_timestamp = None;
+        }
+        assert_eq!(cache.expires(&forecast, issued), issued + DEFAULT_TTL);
+    }
+}
@@ -0,0 +1,156 @@
+//! User preferences, read from a TOML file in the platform's config directory,
+//! e.g. `~/.config/thesurf.in/config.toml` on Linux.
+//!
+//! ```toml
+//! units = "eu,kts"
+//! color = false
+//! provider = "open-meteo"
+//! spots_path = "/path/to/spots.json"
+//!
+//! [[favorites]]
+//! spot = "ormond-beach"
+//! alias = "home"
+//!
+//! [[favorites]]
+//! spot = "mavericks"
+//! ```
+
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use directories::ProjectDirs;
+use serde::Deserialize;
+
+use crate::ui::{ColorDepth, Theme};
+use crate::units::Units;
+
+/// Name of the config file within the config directory
+pub const CONFIG_FILE: &str = "config.toml";
+
+/// Where thesurf.in keeps its config, cache, and data on this platform
+pub fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("", "Sam Tay", "thesurf.in")
+}
+
+/// User preferences. Everything is optional, and command line options take
+/// precedence.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Units to show forecasts in, e.g. `eu` or `m,kts`
+    pub units: Option<Units>,
+    /// Spots to summarize, in order
+    pub favorites: Vec<Favorite>,
+    /// Whether to print colors; otherwise decided by the environment
+    pub color: Option<bool>,
+    /// How many colors the terminal shows: `16`, `256`, or `truecolor`.
+    /// Otherwise decided by the environment, or 16 for the server.
+    pub colors: Option<ColorDepth>,
+    /// Look of the output, see [`Theme`]
+    pub theme: Option<Theme>,
+    /// Name of the forecast provider, see [`crate::provider::PROVIDERS`]
+    pub provider: Option<String>,
+    /// Spots file, as written by the crawler
+    pub spots_path: Option<PathBuf>,
+}
+
+/// A favorite spot, optionally with a shorter name to refer to it by
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Favorite {
+    /// Spot name, e.g. `ormond-beach`
+    pub spot: String,
+    /// Alias to use in place of the spot name, e.g. `home`
+    pub alias: Option<String>,
+}
+
+impl Favorite {
+    /// The alias if there is one, otherwise the spot name
+    pub fn label(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.spot)
+    }
+}
+
+impl Config {
+    /// The config file in the platform's config directory
+    pub fn default_path() -> Option<PathBuf> {
+        project_dirs().map(|dirs| dirs.config_dir().join(CONFIG_FILE))
+    }
+
+    /// Read the config at the given path. A missing file is an empty config.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(contents) => toml::from_str(&contents)
+                .with_context(|| format!("Invalid config file {}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    /// Read the config at the default path, if there is one
+    pub fn load() -> Result<Self> {
+        match Self::default_path() {
+            Some(path) => Self::from_path(path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Resolve a favorite's alias to its spot name. Other names are unchanged.
+    pub fn resolve<'a>(&'a self, name: &'a str) -> &'a str {
+        self.favorites
+            .iter()
+            .find(|fav| fav.alias.as_deref() == Some(name))
+            .map_or(name, |fav| fav.spot.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msw::forecast::{UnitLength, UnitSpeed};
+
+    #[test]
+    fn parses_config() {
+        let config: Config = toml::from_str(
+            r#"
+            units = "eu,kts"
+            color = false
+            colors = "truecolor"
+            theme = "high-contrast"
+
+            [[favorites]]
+            spot = "ormond-beach"
+            alias = "home"
+
+            [[favorites]]
+            spot = "mavericks"
+            "#,
+        )
+        .unwrap();
+        let units = config.units.unwrap();
+        assert_eq!(units.length, Some(UnitLength::Meters));
+        assert_eq!(units.speed, Some(UnitSpeed::Knots));
+        assert_eq!(config.color, Some(false));
+        assert_eq!(config.colors, Some(ColorDepth::TrueColor));
+        assert_eq!(config.theme, Some(Theme::HighContrast));
+        assert_eq!(config.provider, None);
+        let labels: Vec<_> = config.favorites.iter().map(Favorite::label).collect();
+        assert_eq!(labels, vec!["home", "mavericks"]);
+
+        assert_eq!(config.resolve("home"), "ormond-beach");
+        assert_eq!(config.resolve("mavericks"), "mavericks");
+        assert_eq!(config.resolve("pipeline"), "pipeline");
+    }
+
+    #[test]
+    fn missing_file_is_empty() {
+        let config = Config::from_path("/nonexistent/config.toml").unwrap();
+        assert_eq!(config, Config::default());
+        assert!(toml::from_str::<Config>("colour = true").is_err());
+    }
+}
@@ -0,0 +1,197 @@
+//! What changed between two forecast runs for a spot, e.g. to see at a glance
+//! whether the weekend got better since the last model run.
+
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+use crate::archive::Run;
+use crate::msw::forecast::{timestamp_fmt, Forecast, UnitLength, UnitSpeed};
+use crate::units::Units;
+
+/// Smallest change in breaking height worth pointing out
+const HEIGHT_THRESHOLD: f64 = 0.1;
+
+/// Whether a change makes for better or worse surf
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Trend {
+    Upgrade,
+    Downgrade,
+    Same,
+}
+
+/// A value in the earlier run, and in the later one
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Change<T> {
+    pub before: T,
+    pub after: T,
+    pub trend: Trend,
+}
+
+impl<T> Change<T>
+where
+    T: Copy + Into<f64>,
+{
+    /// Bigger is better, unless `lower_is_better`. Changes smaller than the
+    /// threshold are ignored.
+    fn new(before: T, after: T, threshold: f64, lower_is_better: bool) -> Self {
+        let delta = after.into() - before.into();
+        let trend = if delta.abs() < threshold {
+            Trend::Same
+        } else if (delta > 0.0) != lower_is_better {
+            Trend::Upgrade
+        } else {
+            Trend::Downgrade
+        };
+        Self {
+            before,
+            after,
+            trend,
+        }
+    }
+
+    pub fn delta(&self) -> f64 {
+        self.after.into() - self.before.into()
+    }
+}
+
+/// Changes to a single time slot
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlotDiff {
+    pub timestamp: i64,
+    #[serde(serialize_with = "timestamp_fmt::serialize")]
+    pub local_timestamp: NaiveDateTime,
+    /// Max breaking height
+    pub height: Change<f32>,
+    /// Period of the primary swell, if both runs have one
+    pub period: Option<Change<u16>>,
+    /// Wind speed, where less is better
+    pub wind: Change<u32>,
+}
+
+/// Changes between two runs, for the time slots they both forecast
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunDiff {
+    pub before_issued: i64,
+    pub after_issued: i64,
+    pub unit_length: UnitLength,
+    pub unit_speed: UnitSpeed,
+    pub slots: Vec<SlotDiff>,
+}
+
+impl RunDiff {
+    /// Compare an earlier run to a later one, in the later run's units
+    pub fn new(before: &Run, after: &Run) -> Self {
+        let (unit_length, unit_speed) = after
+            .forecast
+            .first()
+            .map_or((UnitLength::Feet, UnitSpeed::Mph), |fc| {
+                (fc.swell.unit, fc.wind.unit)
+            });
+        let units = Units {
+            length: Some(unit_length),
+            speed: Some(unit_speed),
+            ..Units::default()
+        };
+        let slots = after
+            .forecast
+            .iter()
+            .filter_map(|fc| {
+                let prev = before
+                    .forecast
+                    .iter()
+                    .find(|prev| prev.timestamp == fc.timestamp)?;
+                Some(SlotDiff::new(&prev.clone().in_units(&units), fc))
+            })
+            .collect();
+        Self {
+            before_issued: before.issued,
+            after_issued: after.issued,
+            unit_length,
+            unit_speed,
+            slots,
+        }
+    }
+}
+
+impl SlotDiff {
+    fn new(before: &Forecast, after: &Forecast) -> Self {
+        let period = before
+            .swell
+            .components
+            .primary
+            .zip(after.swell.components.primary)
+            .map(|(b, a)| Change::new(b.period, a.period, 1.0, false));
+        Self {
+            timestamp: after.timestamp,
+            local_timestamp: after.local_timestamp,
+            height: Change::new(
+                before.swell.max_breaking_height,
+                after.swell.max_breaking_height,
+                HEIGHT_THRESHOLD,
+                false,
+            ),
+            period,
+            wind: Change::new(before.wind.speed, after.wind.speed, 1.0, true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(issued: i64) -> Run {
+        let json = include_str!("../../test/msw/forecast.json");
+        Run {
+            issued,
+            forecast: serde_json::from_str(json).unwrap(),
+        }
+    }
+
+    #[test]
+    fn classifies_changes() {
+        let before = run(1656979200);
+        let mut after = run(1657000800);
+        // The later run starts one slot later
+        after.forecast.remove(0);
+        after.forecast[0].swell.max_breaking_height += 1.0;
+        after.forecast[0].wind.speed += 5;
+        after.forecast[1].wind.speed = after.forecast[1].wind.speed.saturating_sub(5);
+
+        let diff = RunDiff::new(&before, &after);
+        assert_eq!(diff.slots.len(), before.forecast.len() - 1);
+        let first = &diff.slots[0];
+        assert_eq!(first.timestamp, before.forecast[1].timestamp);
+        assert_eq!(first.height.trend, Trend::Upgrade);
+        assert!((first.height.delta() - 1.0).abs() < 1e-6);
+        assert_eq!(first.wind.trend, Trend::Downgrade);
+        assert_eq!(first.period.unwrap().trend, Trend::Same);
+        assert_eq!(diff.slots[1].wind.trend, Trend::Upgrade);
+        assert_eq!(diff.slots[2].height.trend, Trend::Same);
+    }
+
+    #[test]
+    fn compares_in_later_units() {
+        let before = run(1656979200);
+        let mut after = run(1657000800);
+        let meters = Units {
+            length: Some(UnitLength::Meters),
+            ..Units::default()
+        };
+        after.forecast = after
+            .forecast
+            .into_iter()
+            .map(|fc| fc.in_units(&meters))
+            .collect();
+        let diff = RunDiff::new(&before, &after);
+        assert_eq!(diff.unit_length, UnitLength::Meters);
+        assert!(diff
+            .slots
+            .iter()
+            .all(|slot| slot.height.trend == Trend::Same));
+    }
+}
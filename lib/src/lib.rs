@@ -1,2 +1,16 @@
+pub mod api;
+pub mod archive;
+pub mod cache;
+pub mod config;
+pub mod diff;
 pub mod msw;
+pub mod ndbc;
+pub mod open_meteo;
+pub mod provider;
+pub mod rating;
+pub mod shore;
+pub mod spot;
+pub mod sun;
+pub mod tide;
 pub mod ui;
+pub mod units;
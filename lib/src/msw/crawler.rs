@@ -2,8 +2,16 @@ use anyhow::{anyhow, Context, Result};
 use reqwest::blocking::Client;
 use scraper::html::Html;
 use scraper::selector::Selector;
-use serde_json::{to_writer, Map, Value};
-use std::{collections::HashMap, fs::File, io::Write, path::Path};
+use scraper::ElementRef;
+use serde::Deserialize;
+use std::{fs::File, io::Write, thread, time::Duration};
+
+use crate::spot::{Coordinates, Spot, Spots};
+
+/// Open-Meteo's geocoding API, to locate spots by name
+const GEOCODING_URL: &str = "https://geocoding-api.open-meteo.com/v1/search";
+/// Pause between geocoding requests, as the API allows 600 a minute
+const GEOCODING_INTERVAL: Duration = Duration::from_millis(100);
 
 pub struct Crawler {
     client: Client,
@@ -16,11 +24,15 @@ impl Crawler {
         }
     }
 
-    /// Crawls the sitemap.php and finds spot IDs and names. Writes to the buffer in a JSON format, i.e.
-    /// `{ "ormond-beach": 4203 }`
+    /// Crawls the sitemap.php and finds spot IDs, names and regions, then
+    /// locates each spot by geocoding its name. Writes to the buffer in the
+    /// versioned spots format, i.e. `{ "version": 1, "spots": [{ "id": 4203,
+    /// "slug": "ormond-beach", "lat": 29.28, ... }] }`
+    ///
+    /// Spots that can't be found within their region are written without
+    /// coordinates.
     ///
     /// TODO: support "ormond-beach-fl" too...
-    /// TODO: async and concurrent requests to crawl each spot for lat/long
     pub fn crawl_spot_ids(&self, writer: &mut impl Write) -> Result<()> {
         let html = self
             .client
@@ -29,108 +41,167 @@ impl Crawler {
             .text()?;
         let mut file = File::create("site-map.html")?;
         file.write_all(html.as_bytes())?;
-        parse_spot_ids(&html, writer)
+        let spots = parse_spots(&html)?
+            .into_iter()
+            .map(|spot| {
+                thread::sleep(GEOCODING_INTERVAL);
+                match self.geocode(&spot.name) {
+                    Ok(places) => locate(spot, places),
+                    Err(_) => spot,
+                }
+            })
+            .collect::<Spots>();
+        spots.write(writer)
     }
-}
 
-fn parse_spot_ids<T: Write>(html: &str, writer: T) -> Result<()> {
-    let mut spot_json_map = Map::new();
-    let spot_anchors = Selector::parse("h1.header + table a").unwrap();
-    let document = Html::parse_document(html);
-    for anchor in document.select(&spot_anchors) {
-        let spot_id: u16 = anchor
-            .value()
-            .attr("href")
-            .and_then(|href| href.trim_end_matches('/').rsplit_once('/'))
-            .map(|(_, spot_id)| spot_id.to_owned())
-            .ok_or(anyhow!("Failed to find spot ID in HTML anchor"))
-            .and_then(|s| s.parse().context("Couldn't parse spot ID into integer"))?;
-        let spot_name = anchor
-            .inner_html()
-            .to_lowercase()
-            .replace(|c| matches!(c, '/' | '(' | ')' | '\'' | '-' | '.'), " ")
-            .split_whitespace()
-            .filter(|s| !s.is_empty())
-            .collect::<Vec<_>>()
-            .join("-");
-        spot_json_map.insert(spot_name, spot_id.into());
+    /// Places matching the name, most relevant first
+    fn geocode(&self, name: &str) -> Result<Vec<Place>> {
+        let response: Geocoding = self
+            .client
+            .get(GEOCODING_URL)
+            .query(&[("name", name), ("count", "10"), ("format", "json")])
+            .send()?
+            .error_for_status()?
+            .json()?;
+        Ok(response.results)
     }
-    to_writer(writer, &Value::Object(spot_json_map))?;
-    Ok(())
 }
 
-impl Default for Crawler {
-    fn default() -> Self {
-        Self::new()
-    }
+/// Response of the geocoding API
+#[derive(Deserialize)]
+struct Geocoding {
+    /// Missing when nothing matches
+    #[serde(default)]
+    results: Vec<Place>,
 }
 
-pub struct Spots {
-    spots: HashMap<String, u16>,
+#[derive(Deserialize)]
+struct Place {
+    latitude: f64,
+    longitude: f64,
+    country: Option<String>,
+    /// State, province, or similar
+    admin1: Option<String>,
+    /// County, or similar
+    admin2: Option<String>,
+    timezone: Option<String>,
 }
 
-impl Spots {
-    /// Create a new Spots struct, pulling data from ./data/spots.json
-    pub fn new() -> Result<Self> {
-        Self::from_path("./data/spots.json")
+impl Place {
+    /// Whether the place lies in a site map region, e.g. `Florida - East Coast`
+    fn is_in(&self, region: &str) -> bool {
+        [&self.admin1, &self.admin2, &self.country]
+            .into_iter()
+            .flatten()
+            .any(|area| region.contains(area.as_str()))
     }
+}
 
-    /// Create a new Spots struct, pulling data from the given path
-    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let file = File::open(path.as_ref()).context(format!(
-            "Couldn't find spots json file at {:?}",
-            path.as_ref()
-        ))?;
-        let spots = serde_json::from_reader(file).context(format!(
-            "Couldn't parse file {:?} into spots json",
-            path.as_ref()
-        ))?;
-        Ok(Self { spots })
+/// Fill in the spot's location from the first place in its region. Names
+/// alone are too ambiguous, so spots without a region stay unlocated.
+fn locate(spot: Spot, places: Vec<Place>) -> Spot {
+    let place = match &spot.region {
+        Some(region) => places.into_iter().find(|place| place.is_in(region)),
+        None => None,
+    };
+    let place = match place {
+        Some(place) => place,
+        None => return spot,
+    };
+    let mut spot = spot.coordinates(Coordinates::new(place.latitude, place.longitude));
+    if let Some(country) = place.country {
+        spot = spot.country(country);
+    }
+    if let Some(timezone) = place.timezone {
+        spot = spot.timezone(timezone);
     }
+    spot
+}
 
-    /// Search the spots data for the MSW spot identifier (the integer)
-    pub fn get_id<'a>(&self, name: impl Into<&'a str>) -> Option<u16> {
-        self.spots.get(name.into()).copied()
+/// Spots listed in the site map, each under the heading of its region
+fn parse_spots(html: &str) -> Result<Vec<Spot>> {
+    let mut spots = Vec::new();
+    let headers = Selector::parse("h1.header").unwrap();
+    let anchors = Selector::parse("a").unwrap();
+    let document = Html::parse_document(html);
+    for header in document.select(&headers) {
+        let table = match header.next_siblings().find_map(ElementRef::wrap) {
+            Some(table) if table.value().name() == "table" => table,
+            _ => continue,
+        };
+        let heading = header.text().collect::<String>();
+        let region = heading.trim().trim_end_matches(" Surf Reports");
+        for anchor in table.select(&anchors) {
+            spots.push(parse_anchor(anchor)?.region(region));
+        }
     }
+    Ok(spots)
+}
 
-    /// Iterate over all spots (requires cloning)
-    pub fn into_vec(&self) -> Vec<(String, u16)> {
-        self.spots.clone().into_iter().collect()
+fn parse_anchor(anchor: ElementRef) -> Result<Spot> {
+    let spot_id: u16 = anchor
+        .value()
+        .attr("href")
+        .and_then(|href| href.trim_end_matches('/').rsplit_once('/'))
+        .map(|(_, spot_id)| spot_id.to_owned())
+        .ok_or(anyhow!("Failed to find spot ID in HTML anchor"))
+        .and_then(|s| s.parse().context("Couldn't parse spot ID into integer"))?;
+    let display_name = anchor.text().collect::<String>();
+    let spot_name = anchor
+        .inner_html()
+        .to_lowercase()
+        .replace(['/', '(', ')', '\'', '-', '.'], " ")
+        .split_whitespace()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    Ok(Spot::new(spot_id, spot_name).name(display_name.trim()))
+}
+
+impl Default for Crawler {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serde_json::json;
-    use std::io::Cursor;
 
     #[test]
     fn crawl_works() {
         let html = include_str!("../../../test/msw/site-map.html");
-        let mut buffer = Cursor::new(Vec::new());
-        parse_spot_ids(html, &mut buffer).unwrap();
-        buffer.set_position(0);
-        let spots: Value = serde_json::from_reader(buffer).unwrap();
-        assert_eq!(*spots.get("ormond-beach").unwrap(), json!(4203));
+        let spots = parse_spots(html).unwrap().into_iter().collect::<Spots>();
+        assert_eq!(spots.get_id("ormond-beach"), Some(4203));
+        let spot = spots.find("ormond-beach").unwrap();
+        assert_eq!(spot.name, "Ormond Beach");
+        assert_eq!(spot.region.as_deref(), Some("Florida - East Coast"));
     }
 
     #[test]
     fn name_cleaning_works() {
         let html = include_str!("../../../test/msw/site-map.html");
-        let mut buffer = Cursor::new(Vec::new());
-        parse_spot_ids(html, &mut buffer).unwrap();
-        buffer.set_position(0);
-        let spots: HashMap<String, u16> = serde_json::from_reader(buffer).unwrap();
-        for name in spots.into_keys() {
+        let spots = parse_spots(html).unwrap().into_iter().collect::<Spots>();
+        for (name, _) in spots.into_vec() {
             assert!(!name.contains('/'), "name contains /");
-            assert!(
-                !name.contains(|c| matches!(c, '(' | ')')),
-                "name contains (,)"
-            );
+            assert!(!name.contains(['(', ')']), "name contains (,)");
             assert!(!name.contains('\''), "name contains '");
             assert!(!name.contains("--"), "name contains consecutive hyphens");
             assert!(!name.contains('.'), "name contains .");
         }
     }
+
+    #[test]
+    fn locates_spots_within_their_region() {
+        let json = include_str!("../../../test/open-meteo/geocoding.json");
+        let places = || serde_json::from_str::<Geocoding>(json).unwrap().results;
+        let spot = Spot::new(4203, "ormond-beach").region("Florida - East Coast");
+        let spot = locate(spot, places());
+        let coordinates = spot.coordinates.unwrap();
+        assert_eq!((coordinates.lat, coordinates.lon), (29.28581, -81.05589));
+        assert_eq!(spot.timezone.as_deref(), Some("America/New_York"));
+
+        let spot = Spot::new(1, "ormond-beach").region("Hawaii");
+        assert!(locate(spot, places()).coordinates.is_none());
+    }
 }
@@ -1,16 +1,23 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 use anyhow::{anyhow, Result};
+use async_trait::async_trait;
 use chrono::NaiveDateTime;
 use reqwest::{Client, Url};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Deserialize, PartialEq)]
+use crate::provider::ForecastProvider;
+use crate::spot::Spot;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Forecast {
     pub timestamp: i64,
-    #[serde(deserialize_with = "timestamp_fmt::deserialize")]
+    #[serde(with = "timestamp_fmt")]
     pub local_timestamp: NaiveDateTime,
+    /// When the forecast model run was issued, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issue_timestamp: Option<i64>,
     pub faded_rating: u8, // or custom star rating enum
     pub solid_rating: u8,
     pub swell: Swell,
@@ -19,7 +26,7 @@ pub struct Forecast {
     pub charts: Charts,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Swell {
     pub min_breaking_height: f32,
@@ -30,7 +37,7 @@ pub struct Swell {
     pub components: SwellComponents,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct SwellComponents {
     pub combined: Option<SwellComponent>,
@@ -39,7 +46,7 @@ pub struct SwellComponents {
     pub tertiary: Option<SwellComponent>,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct SwellComponent {
     pub height: f32,
@@ -48,7 +55,7 @@ pub struct SwellComponent {
     pub compass_direction: CompassDirection,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Wind {
     pub speed: u32,
@@ -60,19 +67,19 @@ pub struct Wind {
     pub unit: UnitSpeed,
 }
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Condition {
-    pub pressure: u32,
+    pub pressure: f32,
     #[serde(deserialize_with = "int_fmt::deserialize")]
     pub temperature: i32,
-    pub unit_pressure: String, // display purposes only
+    pub unit_pressure: UnitPressure,
     #[serde(rename = "unit")]
     pub unit_temperature: UnitTemperature,
 }
 
 // or URL types
-#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct Charts {
     pub swell: Option<String>,
@@ -82,7 +89,7 @@ pub struct Charts {
     pub sst: Option<String>,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub enum UnitLength {
     #[serde(rename = "ft")]
     Feet,
@@ -104,6 +111,8 @@ impl Display for UnitSpeed {
         match *self {
             Self::Mph => write!(f, "mph"),
             Self::Kph => write!(f, "kph"),
+            Self::Knots => write!(f, "kts"),
+            Self::MetersPerSecond => write!(f, "m/s"),
         }
     }
 }
@@ -117,21 +126,42 @@ impl Display for UnitTemperature {
     }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum UnitSpeed {
     Mph,
     Kph,
+    #[serde(rename = "kts")]
+    Knots,
+    #[serde(rename = "m/s")]
+    MetersPerSecond,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum UnitTemperature {
     C,
     F,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+pub enum UnitPressure {
+    #[serde(rename = "mb")]
+    Millibars,
+    #[serde(rename = "inHg")]
+    InchesOfMercury,
+}
+
+impl Display for UnitPressure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::Millibars => write!(f, "mb"),
+            Self::InchesOfMercury => write!(f, "inHg"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum CompassDirection {
     N,
@@ -152,6 +182,33 @@ pub enum CompassDirection {
     NNW,
 }
 
+impl CompassDirection {
+    /// All compass points, clockwise from north
+    pub const ALL: [CompassDirection; 16] = {
+        use CompassDirection::*;
+        [
+            N, NNE, NE, ENE, E, ESE, SE, SSE, S, SSW, SW, WSW, W, WNW, NW, NNW,
+        ]
+    };
+
+    /// Nearest compass point to the given bearing in degrees
+    pub fn from_degrees(degrees: f32) -> Self {
+        let ix = ((degrees.rem_euclid(360.0) + 11.25) / 22.5) as usize;
+        Self::ALL[ix % Self::ALL.len()]
+    }
+}
+
+impl FromStr for CompassDirection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|dir| format!("{dir:?}").eq_ignore_ascii_case(s))
+            .ok_or_else(|| anyhow!("Invalid compass direction {s:?}"))
+    }
+}
+
 /// Unit options supported by MSW
 #[derive(Copy, Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
@@ -202,15 +259,34 @@ impl ForecastAPI {
     }
 }
 
+#[async_trait]
+impl ForecastProvider for ForecastAPI {
+    fn name(&self) -> &'static str {
+        "msw"
+    }
+
+    async fn forecast(&self, spot: &Spot) -> Result<Vec<Forecast>> {
+        self.get(spot.id).await
+    }
+}
+
 impl Default for ForecastAPI {
     fn default() -> Self {
         Self::new()
     }
 }
 
-mod timestamp_fmt {
+/// (De)serialize a naive datetime as a unix timestamp
+pub(crate) mod timestamp_fmt {
     use chrono::NaiveDateTime;
-    use serde::{Deserialize, Deserializer};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(datetime: &NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_i64(datetime.timestamp())
+    }
 
     pub fn deserialize<'de, D>(deserializer: D) -> Result<NaiveDateTime, D::Error>
     where
@@ -302,6 +378,7 @@ mod tests {
         let expected_forecast = Forecast {
             timestamp: 1645678800,
             local_timestamp: expected_local_timestamp,
+            issue_timestamp: Some(1645660800),
             faded_rating: 1, // or custom star rating enum
             solid_rating: 1,
             swell: Swell {
@@ -336,10 +413,10 @@ mod tests {
                 unit: UnitSpeed::Mph,
             },
             condition: Condition {
-                pressure: 1023,
+                pressure: 1023.0,
                 temperature: 20,
                 //weather: 10,
-                unit_pressure: "mb".to_string(),
+                unit_pressure: UnitPressure::Millibars,
                 unit_temperature: UnitTemperature::C,
             },
             charts: Charts {
@@ -429,6 +506,7 @@ mod tests {
         let expected_forecast = Forecast {
             timestamp: 1645678800,
             local_timestamp: expected_local_timestamp,
+            issue_timestamp: Some(1645660800),
             faded_rating: 1, // or custom star rating enum
             solid_rating: 1,
             swell: Swell {
@@ -463,10 +541,10 @@ mod tests {
                 unit: UnitSpeed::Mph,
             },
             condition: Condition {
-                pressure: 1023,
+                pressure: 1023.0,
                 temperature: 20,
                 //weather: 10,
-                unit_pressure: "mb".to_string(),
+                unit_pressure: UnitPressure::Millibars,
                 unit_temperature: UnitTemperature::C,
             },
             charts: Charts {
@@ -495,4 +573,12 @@ mod tests {
             expected_forecast.wind.chill
         );
     }
+
+    #[test]
+    fn compass_from_degrees() {
+        assert_eq!(CompassDirection::from_degrees(0.0), CompassDirection::N);
+        assert_eq!(CompassDirection::from_degrees(359.0), CompassDirection::N);
+        assert_eq!(CompassDirection::from_degrees(-90.0), CompassDirection::W);
+        assert_eq!(CompassDirection::from_degrees(200.0), CompassDirection::SSW);
+    }
 }
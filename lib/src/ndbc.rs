@@ -0,0 +1,295 @@
+//! Realtime buoy observations from the NOAA [National Data Buoy
+//! Center](https://www.ndbc.noaa.gov).
+//!
+//! NDBC publishes the last 45 days of observations per station as
+//! whitespace-delimited text, newest first, with `MM` for missing values. The
+//! `.txt` file holds the standard meteorological data and the `.spec` file
+//! breaks the waves down into swell and wind wave components.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::NaiveDateTime;
+use reqwest::{Client, StatusCode, Url};
+use serde::Serialize;
+
+use crate::msw::forecast::{timestamp_fmt, CompassDirection};
+
+pub const REALTIME_URL: &str = "https://www.ndbc.noaa.gov/data/realtime2/";
+
+/// A single observation from a buoy. Units are those reported by NDBC: meters,
+/// seconds, m/s, and °C. Directions are where the waves/wind come from, in
+/// degrees true.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuoyObservation {
+    /// Observation time (UTC)
+    #[serde(serialize_with = "timestamp_fmt::serialize")]
+    pub timestamp: NaiveDateTime,
+    /// Significant wave height (WVHT)
+    pub wave_height: Option<f32>,
+    /// Dominant wave period (DPD)
+    pub dominant_period: Option<f32>,
+    /// Mean wave direction at the dominant period (MWD)
+    pub mean_wave_direction: Option<f32>,
+    /// Wind direction (WDIR)
+    pub wind_direction: Option<f32>,
+    /// Wind speed (WSPD)
+    pub wind_speed: Option<f32>,
+    /// Peak gust speed (GST)
+    pub gusts: Option<f32>,
+    /// Sea surface temperature (WTMP)
+    pub water_temperature: Option<f32>,
+    /// Swell component, from the spectral wave data (SwH, SwP, SwD)
+    pub swell: Option<WaveComponent>,
+    /// Wind wave component, from the spectral wave data (WWH, WWP, WWD)
+    pub wind_wave: Option<WaveComponent>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WaveComponent {
+    pub height: f32,
+    pub period: f32,
+    pub compass_direction: CompassDirection,
+}
+
+/// Observations for a single station, sorted oldest first
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Buoy {
+    pub station: String,
+    pub observations: Vec<BuoyObservation>,
+}
+
+pub struct BuoyAPI {
+    client: Client,
+    base_url: String,
+}
+
+impl BuoyAPI {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+            base_url: REALTIME_URL.to_owned(),
+        }
+    }
+
+    /// Override the realtime data directory, e.g. to point at a stub server
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    /// Gets the latest observations for the given station, e.g. `41009`, or
+    /// `None` if NDBC has no realtime data for the station. Spectral data is
+    /// optional, as not every station reports it.
+    pub async fn get(&self, station: &str) -> Result<Option<Buoy>> {
+        if !is_station_id(station) {
+            bail!("Invalid buoy station {station:?}, expected letters and digits only");
+        }
+        let station = station.to_uppercase();
+        let txt = match self.fetch(&format!("{station}.txt")).await? {
+            Some(txt) => txt,
+            None => return Ok(None),
+        };
+        let spec = self.fetch(&format!("{station}.spec")).await?;
+        let observations = parse_observations(&txt, spec.as_deref())?;
+        Ok(Some(Buoy {
+            station,
+            observations,
+        }))
+    }
+
+    async fn fetch(&self, file: &str) -> Result<Option<String>> {
+        let url = Url::parse(&self.base_url)
+            .and_then(|base| base.join(file))
+            .context(format!("Invalid NDBC URL {:?}", self.base_url))?;
+        let response = self.client.get(url).send().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Ok(Some(response.error_for_status()?.text().await?))
+    }
+}
+
+impl Default for BuoyAPI {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether the station id is safe to put in a URL. NDBC ids are letters and
+/// digits, e.g. `41009` or `FPKG1`.
+pub fn is_station_id(station: &str) -> bool {
+    !station.is_empty() && station.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Parse the contents of realtime2 `.txt` and (optionally) `.spec` files into
+/// observations, oldest first.
+pub fn parse_observations(txt: &str, spec: Option<&str>) -> Result<Vec<BuoyObservation>> {
+    let spec_rows = match spec {
+        Some(spec) => parse_table(spec).context("Couldn't parse NDBC spectral data")?,
+        None => Vec::new(),
+    };
+    let spec_by_time: HashMap<NaiveDateTime, &Row> =
+        spec_rows.iter().map(|row| (row.timestamp, row)).collect();
+
+    let mut observations = parse_table(txt)
+        .context("Couldn't parse NDBC meteorological data")?
+        .iter()
+        .map(|row| {
+            let spec = spec_by_time.get(&row.timestamp);
+            BuoyObservation {
+                timestamp: row.timestamp,
+                wave_height: row.number("WVHT"),
+                dominant_period: row.number("DPD"),
+                mean_wave_direction: row.number("MWD"),
+                wind_direction: row.number("WDIR"),
+                wind_speed: row.number("WSPD"),
+                gusts: row.number("GST"),
+                water_temperature: row.number("WTMP"),
+                swell: spec.and_then(|s| s.component("SwH", "SwP", "SwD")),
+                wind_wave: spec.and_then(|s| s.component("WWH", "WWP", "WWD")),
+            }
+        })
+        .collect::<Vec<_>>();
+    observations.sort_by_key(|obs| obs.timestamp);
+    Ok(observations)
+}
+
+/// A row of an NDBC table, with values keyed by column header
+struct Row<'a> {
+    timestamp: NaiveDateTime,
+    values: HashMap<&'a str, &'a str>,
+}
+
+impl Row<'_> {
+    fn value(&self, column: &str) -> Option<&str> {
+        self.values.get(column).copied().filter(|v| *v != "MM")
+    }
+
+    fn number(&self, column: &str) -> Option<f32> {
+        self.value(column).and_then(|v| v.parse().ok())
+    }
+
+    fn component(&self, height: &str, period: &str, direction: &str) -> Option<WaveComponent> {
+        Some(WaveComponent {
+            height: self.number(height)?,
+            period: self.number(period)?,
+            compass_direction: self.value(direction)?.parse().ok()?,
+        })
+    }
+}
+
+/// The first line of the file holds the column headers, the second the units
+fn parse_table(contents: &str) -> Result<Vec<Row<'_>>> {
+    let mut lines = contents.lines();
+    let headers = lines
+        .next()
+        .and_then(|line| line.strip_prefix('#'))
+        .ok_or_else(|| anyhow!("Missing header line"))?
+        .split_whitespace()
+        .collect::<Vec<_>>();
+    lines
+        .filter(|line| !line.starts_with('#') && !line.trim().is_empty())
+        .map(|line| {
+            let values: HashMap<&str, &str> = headers
+                .iter()
+                .copied()
+                .zip(line.split_whitespace())
+                .collect();
+            let date = ["YY", "MM", "DD", "hh", "mm"]
+                .iter()
+                .map(|col| values.get(col).copied())
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| anyhow!("Missing date columns in {line:?}"))?
+                .join(" ");
+            let timestamp = NaiveDateTime::parse_from_str(&date, "%Y %m %d %H %M")
+                .context(format!("Couldn't parse observation time {date:?}"))?;
+            Ok(Row { timestamp, values })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observations() -> Vec<BuoyObservation> {
+        parse_observations(
+            include_str!("../../test/ndbc/41009.txt"),
+            Some(include_str!("../../test/ndbc/41009.spec")),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_realtime_files() {
+        let observations = observations();
+        assert_eq!(observations.len(), 288);
+        assert!(observations
+            .windows(2)
+            .all(|w| w[0].timestamp < w[1].timestamp));
+
+        let latest = observations.last().unwrap();
+        assert_eq!(latest.timestamp.to_string(), "2024-07-03 16:50:00");
+        assert_eq!(latest.wave_height, None);
+        assert_eq!(latest.wind_direction, Some(160.0));
+        assert_eq!(latest.wind_speed, Some(5.0));
+        assert_eq!(latest.gusts, Some(6.5));
+        assert_eq!(latest.water_temperature, Some(28.9));
+
+        let latest_waves = &observations[observations.len() - 2];
+        assert_eq!(latest_waves.wave_height, Some(1.1));
+        assert_eq!(latest_waves.dominant_period, Some(8.0));
+        assert_eq!(latest_waves.mean_wave_direction, Some(95.0));
+        assert_eq!(
+            latest_waves.swell,
+            Some(WaveComponent {
+                height: 0.9,
+                period: 8.3,
+                compass_direction: CompassDirection::E,
+            })
+        );
+    }
+
+    #[test]
+    fn missing_values_are_none() {
+        let observations = observations();
+        let no_wind = observations
+            .iter()
+            .find(|obs| obs.timestamp.to_string() == "2024-07-03 10:40:00")
+            .unwrap();
+        assert!(no_wind.wave_height.is_some());
+        assert_eq!(no_wind.wind_speed, None);
+        assert_eq!(no_wind.gusts, None);
+
+        let no_spec = observations
+            .iter()
+            .find(|obs| obs.timestamp.to_string() == "2024-07-03 00:40:00")
+            .unwrap();
+        assert!(no_spec.wave_height.is_some());
+        assert_eq!(no_spec.swell, None);
+        assert_eq!(no_spec.wind_wave, None);
+    }
+
+    #[test]
+    fn spectral_data_is_optional() {
+        let observations =
+            parse_observations(include_str!("../../test/ndbc/41009.txt"), None).unwrap();
+        assert!(observations.iter().all(|obs| obs.swell.is_none()));
+    }
+
+    #[tokio::test]
+    async fn rejects_station_ids_outside_alphanumerics() {
+        assert!(is_station_id("41009"));
+        assert!(is_station_id("fpkg1"));
+        // Never leaves the machine, so the base URL doesn't matter
+        let api = BuoyAPI::new().base_url("http://127.0.0.1:9/");
+        for station in ["", "../41009", "41009?x=1", "https://evil.test/x"] {
+            let err = api.get(station).await.unwrap_err();
+            assert!(err.to_string().contains("Invalid buoy station"), "{err}");
+        }
+    }
+}
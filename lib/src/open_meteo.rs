@@ -0,0 +1,485 @@
+//! Forecasts from the free [Open-Meteo](https://open-meteo.com) marine and
+//! weather APIs, mapped into the MSW forecast model.
+//!
+//! Open-Meteo reports up to three swells and the local wind waves separately,
+//! where MSW has room for three swell components. When there's no third swell
+//! the wind waves take the tertiary component, so short period chop still
+//! shows up in the swell breakdown.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Timelike};
+use reqwest::{Client, Url};
+use serde::Deserialize;
+
+use crate::msw::forecast::{
+    Charts, CompassDirection, Condition, Forecast, Swell, SwellComponent, SwellComponents,
+    UnitLength, UnitPressure, UnitSpeed, UnitTemperature, UnitType, Wind,
+};
+use crate::provider::ForecastProvider;
+use crate::spot::{Coordinates, Spot};
+
+pub const MARINE_URL: &str = "https://marine-api.open-meteo.com/v1/marine";
+pub const WEATHER_URL: &str = "https://api.open-meteo.com/v1/forecast";
+
+const MARINE_VARIABLES: &str = "wave_height,wave_direction,wave_period,\
+    swell_wave_height,swell_wave_direction,swell_wave_period,\
+    secondary_swell_wave_height,secondary_swell_wave_direction,secondary_swell_wave_period,\
+    tertiary_swell_wave_height,tertiary_swell_wave_direction,tertiary_swell_wave_period,\
+    wind_wave_height,wind_wave_direction,wind_wave_period";
+const WEATHER_VARIABLES: &str = "temperature_2m,apparent_temperature,pressure_msl,\
+    wind_speed_10m,wind_direction_10m,wind_gusts_10m";
+
+/// MSW forecasts come in 3 hour intervals, and the views assume as much
+const INTERVAL_HOURS: u32 = 3;
+/// MSW forecasts span 5 days, which is as many 3 hour intervals as the graph
+/// view can fit
+const FORECAST_DAYS: &str = "5";
+
+pub struct OpenMeteo {
+    client: Client,
+    marine_url: String,
+    weather_url: String,
+    units: Option<UnitType>,
+}
+
+impl OpenMeteo {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+            marine_url: MARINE_URL.to_owned(),
+            weather_url: WEATHER_URL.to_owned(),
+            units: None,
+        }
+    }
+
+    pub fn units(mut self, unit_type: Option<UnitType>) -> Self {
+        self.units = unit_type;
+        self
+    }
+
+    /// Override the marine API endpoint, e.g. to point at a stub server
+    pub fn marine_url(mut self, url: impl Into<String>) -> Self {
+        self.marine_url = url.into();
+        self
+    }
+
+    /// Override the weather API endpoint, e.g. to point at a stub server
+    pub fn weather_url(mut self, url: impl Into<String>) -> Self {
+        self.weather_url = url.into();
+        self
+    }
+
+    /// Gets the forecast for the given location
+    ///
+    /// https://open-meteo.com/en/docs/marine-weather-api
+    pub async fn get(&self, coordinates: Coordinates) -> Result<Vec<Forecast>> {
+        let (length, speed, temperature) = match self.units {
+            None | Some(UnitType::Eu) => ("metric", "kmh", "celsius"),
+            Some(UnitType::Uk) => ("imperial", "mph", "celsius"),
+            Some(UnitType::Us) => ("imperial", "mph", "fahrenheit"),
+        };
+        let marine_url = self.url(
+            &self.marine_url,
+            coordinates,
+            &[("hourly", MARINE_VARIABLES), ("length_unit", length)],
+        )?;
+        let weather_url = self.url(
+            &self.weather_url,
+            coordinates,
+            &[
+                ("hourly", WEATHER_VARIABLES),
+                ("wind_speed_unit", speed),
+                ("temperature_unit", temperature),
+            ],
+        )?;
+        let marine = self.client.get(marine_url).send().await?;
+        let marine = marine.error_for_status()?.json().await?;
+        let weather = self.client.get(weather_url).send().await?;
+        let weather = weather.error_for_status()?.json().await?;
+        to_forecast(marine, weather)
+    }
+
+    fn url(&self, base: &str, coordinates: Coordinates, params: &[(&str, &str)]) -> Result<Url> {
+        let mut url = Url::parse(base).context(format!("Invalid Open-Meteo URL {base:?}"))?;
+        url.query_pairs_mut()
+            .append_pair("latitude", &coordinates.lat.to_string())
+            .append_pair("longitude", &coordinates.lon.to_string())
+            .append_pair("timezone", "auto")
+            .append_pair("timeformat", "unixtime")
+            .append_pair("forecast_days", FORECAST_DAYS)
+            .extend_pairs(params);
+        Ok(url)
+    }
+}
+
+impl Default for OpenMeteo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ForecastProvider for OpenMeteo {
+    fn name(&self) -> &'static str {
+        "open-meteo"
+    }
+
+    async fn forecast(&self, spot: &Spot) -> Result<Vec<Forecast>> {
+        let coordinates = spot
+            .coordinates
+            .ok_or_else(|| anyhow!("Spot {} has no coordinates", spot.slug))?;
+        self.get(coordinates).await
+    }
+}
+
+/// Response from the marine API
+#[derive(Clone, Debug, Deserialize)]
+pub struct MarineResponse {
+    pub utc_offset_seconds: i64,
+    pub hourly_units: HashMap<String, String>,
+    pub hourly: MarineHourly,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MarineHourly {
+    pub time: Vec<i64>,
+    pub wave_height: Vec<Option<f32>>,
+    pub wave_direction: Vec<Option<f32>>,
+    pub wave_period: Vec<Option<f32>>,
+    pub swell_wave_height: Vec<Option<f32>>,
+    pub swell_wave_direction: Vec<Option<f32>>,
+    pub swell_wave_period: Vec<Option<f32>>,
+    pub secondary_swell_wave_height: Vec<Option<f32>>,
+    pub secondary_swell_wave_direction: Vec<Option<f32>>,
+    pub secondary_swell_wave_period: Vec<Option<f32>>,
+    pub tertiary_swell_wave_height: Vec<Option<f32>>,
+    pub tertiary_swell_wave_direction: Vec<Option<f32>>,
+    pub tertiary_swell_wave_period: Vec<Option<f32>>,
+    pub wind_wave_height: Vec<Option<f32>>,
+    pub wind_wave_direction: Vec<Option<f32>>,
+    pub wind_wave_period: Vec<Option<f32>>,
+}
+
+/// Response from the weather API
+#[derive(Clone, Debug, Deserialize)]
+pub struct WeatherResponse {
+    pub hourly_units: HashMap<String, String>,
+    pub hourly: WeatherHourly,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct WeatherHourly {
+    pub time: Vec<i64>,
+    pub temperature_2m: Vec<Option<f32>>,
+    pub apparent_temperature: Vec<Option<f32>>,
+    pub pressure_msl: Vec<Option<f32>>,
+    pub wind_speed_10m: Vec<Option<f32>>,
+    pub wind_direction_10m: Vec<Option<f32>>,
+    pub wind_gusts_10m: Vec<Option<f32>>,
+}
+
+/// Combine the hourly marine and weather responses into 3 hourly forecasts.
+/// Hours missing any of the essential swell or wind values are skipped.
+pub fn to_forecast(marine: MarineResponse, weather: WeatherResponse) -> Result<Vec<Forecast>> {
+    let unit = length_unit(&marine.hourly_units, "wave_height")?;
+    let wind_unit = speed_unit(&weather.hourly_units, "wind_speed_10m")?;
+    let unit_temperature = temperature_unit(&weather.hourly_units, "temperature_2m")?;
+    let weather_ix: HashMap<i64, usize> = weather
+        .hourly
+        .time
+        .iter()
+        .enumerate()
+        .map(|(ix, t)| (*t, ix))
+        .collect();
+
+    let m = &marine.hourly;
+    let w = &weather.hourly;
+    let mut forecast = Vec::new();
+    for (i, &timestamp) in m.time.iter().enumerate() {
+        let local_timestamp =
+            NaiveDateTime::from_timestamp_opt(timestamp + marine.utc_offset_seconds, 0)
+                .ok_or_else(|| anyhow!("Timestamp {timestamp} out of range"))?;
+        if local_timestamp.hour() % INTERVAL_HOURS != 0 {
+            continue;
+        }
+        let j = match weather_ix.get(&timestamp) {
+            Some(j) => *j,
+            None => continue,
+        };
+        let combined = match component(
+            at(&m.wave_height, i),
+            at(&m.wave_period, i),
+            at(&m.wave_direction, i),
+        ) {
+            Some(c) => c,
+            None => continue,
+        };
+        let (speed, direction) = match (at(&w.wind_speed_10m, j), at(&w.wind_direction_10m, j)) {
+            (Some(s), Some(d)) => (s, d),
+            _ => continue,
+        };
+        let temperature = at(&w.temperature_2m, j).unwrap_or_default();
+
+        let (abs_min_breaking_height, abs_max_breaking_height) =
+            breaking_height(combined.height, combined.period, unit);
+        let (solid_rating, faded_rating) = stars(abs_max_breaking_height, unit, speed, wind_unit);
+        forecast.push(Forecast {
+            timestamp,
+            local_timestamp,
+            issue_timestamp: None,
+            faded_rating,
+            solid_rating,
+            swell: Swell {
+                min_breaking_height: abs_min_breaking_height.round(),
+                abs_min_breaking_height,
+                max_breaking_height: abs_max_breaking_height.round(),
+                abs_max_breaking_height,
+                unit,
+                components: SwellComponents {
+                    combined: Some(combined),
+                    primary: component(
+                        at(&m.swell_wave_height, i),
+                        at(&m.swell_wave_period, i),
+                        at(&m.swell_wave_direction, i),
+                    ),
+                    secondary: component(
+                        at(&m.secondary_swell_wave_height, i),
+                        at(&m.secondary_swell_wave_period, i),
+                        at(&m.secondary_swell_wave_direction, i),
+                    ),
+                    tertiary: component(
+                        at(&m.tertiary_swell_wave_height, i),
+                        at(&m.tertiary_swell_wave_period, i),
+                        at(&m.tertiary_swell_wave_direction, i),
+                    )
+                    // Wind waves, when there's no third swell
+                    .or_else(|| {
+                        component(
+                            at(&m.wind_wave_height, i),
+                            at(&m.wind_wave_period, i),
+                            at(&m.wind_wave_direction, i),
+                        )
+                    }),
+                },
+            },
+            wind: Wind {
+                speed: speed.round() as u32,
+                direction: travel_direction(direction),
+                compass_direction: CompassDirection::from_degrees(direction),
+                chill: at(&w.apparent_temperature, j)
+                    .unwrap_or(temperature)
+                    .round() as i32,
+                gusts: at(&w.wind_gusts_10m, j).unwrap_or(speed).round() as u32,
+                unit: wind_unit,
+            },
+            condition: Condition {
+                pressure: at(&w.pressure_msl, j).unwrap_or_default().round(),
+                temperature: temperature.round() as i32,
+                unit_pressure: UnitPressure::Millibars,
+                unit_temperature,
+            },
+            charts: Charts {
+                swell: None,
+                period: None,
+                wind: None,
+                pressure: None,
+                sst: None,
+            },
+        });
+    }
+    Ok(forecast)
+}
+
+fn at(values: &[Option<f32>], ix: usize) -> Option<f32> {
+    values.get(ix).copied().flatten()
+}
+
+/// Open-Meteo directions are where the swell/wind comes from, which MSW
+/// conveys as the compass direction. MSW's degrees are the direction of travel.
+fn component(
+    height: Option<f32>,
+    period: Option<f32>,
+    direction: Option<f32>,
+) -> Option<SwellComponent> {
+    Some(SwellComponent {
+        height: height?,
+        period: period?.round() as u16,
+        direction: travel_direction(direction?),
+        compass_direction: CompassDirection::from_degrees(direction?),
+    })
+}
+
+fn travel_direction(from_degrees: f32) -> f32 {
+    (from_degrees + 180.0).rem_euclid(360.0)
+}
+
+fn length_unit(units: &HashMap<String, String>, key: &str) -> Result<UnitLength> {
+    match units.get(key).map(String::as_str) {
+        Some("m") => Ok(UnitLength::Meters),
+        Some("ft") => Ok(UnitLength::Feet),
+        other => bail!("Unsupported length unit {other:?} for {key}"),
+    }
+}
+
+fn speed_unit(units: &HashMap<String, String>, key: &str) -> Result<UnitSpeed> {
+    match units.get(key).map(String::as_str) {
+        Some("km/h") => Ok(UnitSpeed::Kph),
+        Some("mph") => Ok(UnitSpeed::Mph),
+        Some("kn") => Ok(UnitSpeed::Knots),
+        Some("m/s") => Ok(UnitSpeed::MetersPerSecond),
+        other => bail!("Unsupported speed unit {other:?} for {key}"),
+    }
+}
+
+fn temperature_unit(units: &HashMap<String, String>, key: &str) -> Result<UnitTemperature> {
+    match units.get(key).map(String::as_str) {
+        Some("°C") => Ok(UnitTemperature::C),
+        Some("°F") => Ok(UnitTemperature::F),
+        other => bail!("Unsupported temperature unit {other:?} for {key}"),
+    }
+}
+
+/// Estimate the (min, max) breaking wave height from the offshore wave height
+/// and period, using the Komar & Gaughan (1972) approximation.
+fn breaking_height(height: f32, period: u16, unit: UnitLength) -> (f32, f32) {
+    const GRAVITY: f32 = 9.81;
+    let height_m = unit.convert(height, UnitLength::Meters);
+    let max_m = 0.39 * GRAVITY.powf(0.2) * (period as f32 * height_m.powi(2)).powf(0.4);
+    let max = UnitLength::Meters.convert(max_m, unit);
+    (max * 0.65, max)
+}
+
+/// Open-Meteo has no notion of surf quality, so approximate MSW's (solid,
+/// faded) star ratings from the breaking height and wind speed.
+fn stars(breaking_height: f32, unit: UnitLength, wind: f32, wind_unit: UnitSpeed) -> (u8, u8) {
+    let height_m = unit.convert(breaking_height, UnitLength::Meters);
+    let wind_kph = wind_unit.convert(wind, UnitSpeed::Kph);
+    let potential: u8 = match height_m {
+        h if h < 0.6 => 0,
+        h if h < 1.2 => 1,
+        h if h < 2.0 => 2,
+        _ => 3,
+    };
+    let blown_out = match wind_kph {
+        w if w < 10.0 => 0,
+        w if w < 20.0 => 1,
+        _ => potential,
+    }
+    .min(potential);
+    (potential - blown_out, blown_out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    const MARINE_JSON: &str = include_str!("../../test/open-meteo/marine.json");
+    const WEATHER_JSON: &str = include_str!("../../test/open-meteo/weather.json");
+
+    fn recorded() -> Vec<Forecast> {
+        to_forecast(
+            serde_json::from_str(MARINE_JSON).unwrap(),
+            serde_json::from_str(WEATHER_JSON).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn maps_into_3_hourly_forecast() {
+        let forecast = recorded();
+        // 5 days of 8 slots, minus the hour missing a wave height
+        assert_eq!(forecast.len(), 39);
+        assert!(forecast
+            .iter()
+            .all(|fc| fc.local_timestamp.hour() % INTERVAL_HOURS == 0));
+        let first = &forecast[0];
+        assert_eq!(first.local_timestamp.to_string(), "2024-07-01 00:00:00");
+        assert_eq!(first.swell.unit, UnitLength::Meters);
+        assert_eq!(first.wind.unit, UnitSpeed::Kph);
+        assert_eq!(first.condition.unit_temperature, UnitTemperature::C);
+        assert!(first.swell.abs_min_breaking_height < first.swell.abs_max_breaking_height);
+        assert!(first.swell.components.secondary.is_none());
+        assert!(first.swell.components.tertiary.is_some());
+    }
+
+    #[test]
+    fn directions_follow_msw_conventions() {
+        let forecast = recorded();
+        let primary = forecast[0].swell.components.primary.unwrap();
+        // Swell from the east, travelling west
+        assert_eq!(primary.compass_direction, CompassDirection::E);
+        assert!((primary.direction - 265.0).abs() < 1.0);
+    }
+
+    /// Serve the recorded responses from a local stub server
+    fn stub_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for stream in listener.incoming().take(2) {
+                let mut stream = stream.unwrap();
+                let mut request_line = String::new();
+                BufReader::new(&stream)
+                    .read_line(&mut request_line)
+                    .unwrap();
+                let body = if request_line.contains("/v1/marine") {
+                    MARINE_JSON
+                } else {
+                    WEATHER_JSON
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        format!("http://{addr}")
+    }
+
+    #[tokio::test]
+    async fn provider_works_against_stub_server() {
+        let base = stub_server();
+        let provider = OpenMeteo::new()
+            .marine_url(format!("{base}/v1/marine"))
+            .weather_url(format!("{base}/v1/forecast"));
+        let spot = Spot::new(4203, "ormond-beach").coordinates(Coordinates::new(29.28, -81.05));
+        let forecast = provider.forecast(&spot).await.unwrap();
+        assert_eq!(forecast, recorded());
+
+        // Wind waves fill in for a missing third swell
+        let marine: MarineResponse = serde_json::from_str(MARINE_JSON).unwrap();
+        let m = &marine.hourly;
+        let mut wind_waves = 0;
+        for fc in &forecast {
+            let i = m.time.iter().position(|t| *t == fc.timestamp).unwrap();
+            if m.tertiary_swell_wave_height[i].is_none() {
+                let expected = component(
+                    m.wind_wave_height[i],
+                    m.wind_wave_period[i],
+                    m.wind_wave_direction[i],
+                );
+                assert!(expected.is_some());
+                assert_eq!(fc.swell.components.tertiary, expected);
+                wind_waves += 1;
+            }
+        }
+        assert!(wind_waves > 0);
+    }
+
+    #[tokio::test]
+    async fn provider_requires_coordinates() {
+        let spot = Spot::new(4203, "ormond-beach");
+        assert!(OpenMeteo::new().forecast(&spot).await.is_err());
+    }
+}
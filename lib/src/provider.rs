@@ -0,0 +1,93 @@
+//! Sources of forecast data
+
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+
+use crate::msw::forecast::{Forecast, ForecastAPI};
+use crate::open_meteo::OpenMeteo;
+use crate::spot::Spot;
+
+/// Anything that can produce a forecast for a spot. The server, CLI and tests
+/// should depend on this rather than any one upstream API.
+#[async_trait]
+pub trait ForecastProvider: Send + Sync {
+    /// Short identifier for this provider, e.g. `msw`
+    fn name(&self) -> &'static str;
+
+    /// Gets the forecast for the given spot
+    async fn forecast(&self, spot: &Spot) -> Result<Vec<Forecast>>;
+}
+
+/// Names of the upstream providers that can be chosen with [`from_name`]
+pub const PROVIDERS: [&str; 2] = ["msw", "open-meteo"];
+
+/// Provider used when neither the command line nor the config picks one
+pub const DEFAULT: &str = "open-meteo";
+
+/// Construct an upstream provider by its name
+pub fn from_name(name: &str) -> Result<Arc<dyn ForecastProvider>> {
+    Ok(match name {
+        "msw" => Arc::new(ForecastAPI::new()),
+        "open-meteo" => Arc::new(OpenMeteo::new()),
+        other => bail!(
+            "Unknown provider {other:?}, expected one of {}",
+            PROVIDERS.join(", ")
+        ),
+    })
+}
+
+/// A provider that always returns the same, previously recorded forecast,
+/// regardless of the spot requested. Useful for demos and tests.
+pub struct Recorded {
+    forecast: Vec<Forecast>,
+}
+
+impl Recorded {
+    pub fn new(forecast: Vec<Forecast>) -> Self {
+        Self { forecast }
+    }
+
+    /// Parse a recorded forecast in the MSW JSON format
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(Self::new(serde_json::from_str(json)?))
+    }
+}
+
+#[async_trait]
+impl ForecastProvider for Recorded {
+    fn name(&self) -> &'static str {
+        "recorded"
+    }
+
+    async fn forecast(&self, _spot: &Spot) -> Result<Vec<Forecast>> {
+        Ok(self.forecast.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn recorded_works_as_trait_object() {
+        let json = include_str!("../../test/msw/forecast.json");
+        let provider: Box<dyn ForecastProvider> = Box::new(Recorded::from_json(json).unwrap());
+        let forecast = provider
+            .forecast(&Spot::new(4203, "ormond-beach"))
+            .await
+            .unwrap();
+        assert_eq!(provider.name(), "recorded");
+        assert_eq!(forecast.len(), 40);
+    }
+
+    #[test]
+    fn providers_by_name() {
+        for name in PROVIDERS {
+            assert_eq!(from_name(name).unwrap().name(), name);
+        }
+        assert!(from_name("surfline").is_err());
+        assert!(PROVIDERS.contains(&DEFAULT));
+    }
+}
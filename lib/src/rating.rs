@@ -0,0 +1,452 @@
+//! Surf quality ratings, independent of any provider's own star ratings.
+//!
+//! Each spot can have a [`Profile`] describing the conditions it works in, e.g.
+//! "works on SW 12s+, low-mid tide, wind < 10 offshore". A [`Rater`] scores
+//! each forecast against the profile, and explains the score with reasons.
+
+use serde::{Deserialize, Serialize};
+
+use crate::msw::forecast::{CompassDirection, Forecast, UnitLength, UnitSpeed};
+use crate::shore::{SwellExposure, WindShore};
+use crate::spot::Spot;
+use crate::tide::{Station, TideExtreme, TideKind};
+
+/// The highest possible score
+pub const MAX_STARS: u8 = 5;
+
+/// Seconds either side of a forecast to look for the surrounding high and low
+const TIDE_SEARCH_WINDOW: i64 = 13 * 60 * 60;
+
+/// The conditions a spot works in. Heights are in meters and speeds in kph,
+/// regardless of the units of the forecast.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Profile {
+    /// Smallest rideable breaking wave height
+    #[serde(alias = "min_height")]
+    pub min_height: f32,
+    /// Height at which the spot closes out
+    #[serde(alias = "max_height", skip_serializing_if = "Option::is_none")]
+    pub max_height: Option<f32>,
+    /// Shortest period of primary swell that breaks well
+    #[serde(alias = "min_period", skip_serializing_if = "Option::is_none")]
+    pub min_period: Option<u16>,
+    /// Directions the primary swell should come from; any if empty
+    #[serde(alias = "swell_directions", skip_serializing_if = "Vec::is_empty")]
+    pub swell_directions: Vec<CompassDirection>,
+    /// Acceptable winds relative to the shore; offshore and cross-offshore if
+    /// empty
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub winds: Vec<WindShore>,
+    /// Wind speed above which conditions are blown out
+    #[serde(alias = "max_wind_speed", skip_serializing_if = "Option::is_none")]
+    pub max_wind_speed: Option<f32>,
+    /// Tide levels the spot works on; any if empty
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tides: Vec<TideLevel>,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            min_height: 0.5,
+            max_height: None,
+            min_period: None,
+            swell_directions: Vec::new(),
+            winds: Vec::new(),
+            max_wind_speed: Some(25.0),
+            tides: Vec::new(),
+        }
+    }
+}
+
+/// Tide level, in thirds of the range between the surrounding low and high
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TideLevel {
+    Low,
+    Mid,
+    High,
+}
+
+/// The aspect of the forecast that a reason refers to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Factor {
+    Height,
+    Period,
+    Direction,
+    Wind,
+    Tide,
+}
+
+/// Why a forecast was rated up or down
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reason {
+    pub factor: Factor,
+    /// Whether this counted for or against the rating
+    pub good: bool,
+    pub detail: String,
+}
+
+impl Reason {
+    fn good(factor: Factor, detail: impl Into<String>) -> Self {
+        Self {
+            factor,
+            good: true,
+            detail: detail.into(),
+        }
+    }
+
+    fn bad(factor: Factor, detail: impl Into<String>) -> Self {
+        Self {
+            factor,
+            good: false,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// A star score out of [`MAX_STARS`] and the reasons for it
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rating {
+    pub stars: u8,
+    /// The stars bucketed, for display
+    pub level: Level,
+    pub reasons: Vec<Reason>,
+}
+
+impl Rating {
+    fn new(stars: u8, reasons: Vec<Reason>) -> Self {
+        Self {
+            stars,
+            level: Level::of(stars),
+            reasons,
+        }
+    }
+}
+
+/// How worthwhile the conditions are, coarser than stars
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Poor,
+    Fair,
+    /// Conditions worth paddling out for
+    Good,
+}
+
+impl Level {
+    /// The level of a star score
+    pub fn of(stars: u8) -> Self {
+        match stars {
+            0 | 1 => Level::Poor,
+            2 | 3 => Level::Fair,
+            _ => Level::Good,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Level::Poor => "poor",
+            Level::Fair => "fair",
+            Level::Good => "good",
+        }
+    }
+}
+
+/// Rates forecasts against a spot's profile
+#[derive(Clone, Debug, Default)]
+pub struct Rater {
+    profile: Profile,
+    bearing: Option<f32>,
+    tides: Option<Station>,
+    /// Highs and lows predicted ahead of time, and the period they cover
+    extremes: Option<(i64, i64, Vec<TideExtreme>)>,
+}
+
+impl Rater {
+    pub fn new(profile: Profile) -> Self {
+        Self {
+            profile,
+            bearing: None,
+            tides: None,
+            extremes: None,
+        }
+    }
+
+    /// Rate using the spot's profile (or the default), shore bearing, and tides
+    pub fn for_spot(spot: &Spot) -> Self {
+        let mut rater = Self::new(spot.profile.clone().unwrap_or_default());
+        rater.bearing = spot.bearing;
+        rater.tides = spot.tides();
+        rater
+    }
+
+    /// Judge wind and swell direction relative to a shore with the given
+    /// beach-facing bearing
+    pub fn shore(mut self, bearing: f32) -> Self {
+        self.bearing = Some(bearing);
+        self
+    }
+
+    /// Judge tide levels using predictions from the given station
+    pub fn tides(mut self, station: Station) -> Self {
+        self.tides = Some(station);
+        self
+    }
+
+    /// Predict the highs and lows around the forecast's times once, rather
+    /// than for every slot rated. Call after choosing the station.
+    pub fn range(mut self, forecast: &[Forecast]) -> Self {
+        let start = forecast.iter().map(|fc| fc.timestamp).min();
+        let end = forecast.iter().map(|fc| fc.timestamp).max();
+        if let (Some(station), Some(start), Some(end)) = (&self.tides, start, end) {
+            let (start, end) = (start - TIDE_SEARCH_WINDOW, end + TIDE_SEARCH_WINDOW);
+            self.extremes = Some((start, end, station.extremes(start, end)));
+        }
+        self
+    }
+
+    pub fn rate(&self, fc: &Forecast) -> Rating {
+        let mut reasons = Vec::new();
+
+        let height = fc
+            .swell
+            .unit
+            .convert(fc.swell.max_breaking_height, UnitLength::Meters);
+        if height < self.profile.min_height {
+            reasons.push(Reason::bad(Factor::Height, "Too small"));
+            return Rating::new(0, reasons);
+        }
+        // Size sets the baseline, and conditions adjust it
+        let mut score: i32 = match height / self.profile.min_height {
+            r if r < 2.0 => 1,
+            r if r < 3.0 => 2,
+            _ => 3,
+        };
+        match self.profile.max_height {
+            Some(max) if height > max => {
+                reasons.push(Reason::bad(Factor::Height, "Too big"));
+                score = 1;
+            }
+            _ => reasons.push(Reason::good(Factor::Height, "Rideable size")),
+        }
+
+        if let Some(primary) = fc.swell.components.primary {
+            if let Some(min_period) = self.profile.min_period {
+                if primary.period >= min_period {
+                    score += 1;
+                    reasons.push(Reason::good(
+                        Factor::Period,
+                        format!("{}s period", primary.period),
+                    ));
+                } else {
+                    score -= 1;
+                    reasons.push(Reason::bad(
+                        Factor::Period,
+                        format!("Short {}s period", primary.period),
+                    ));
+                }
+            }
+
+            let exposure = self
+                .bearing
+                .map(|bearing| SwellExposure::classify(bearing, &primary));
+            let direction = primary.compass_direction;
+            if exposure == Some(SwellExposure::Blocked) {
+                score -= 2;
+                reasons.push(Reason::bad(
+                    Factor::Direction,
+                    format!("{direction:?} swell can't reach the beach"),
+                ));
+            } else if !self.profile.swell_directions.is_empty() {
+                if self.profile.swell_directions.contains(&direction) {
+                    score += 1;
+                    reasons.push(Reason::good(
+                        Factor::Direction,
+                        format!("{direction:?} swell"),
+                    ));
+                } else {
+                    score -= 1;
+                    reasons.push(Reason::bad(
+                        Factor::Direction,
+                        format!("Wrong {direction:?} swell direction"),
+                    ));
+                }
+            }
+        }
+
+        let speed = fc.wind.unit.convert(fc.wind.speed as f32, UnitSpeed::Kph);
+        let blown_out = self.profile.max_wind_speed.is_some_and(|max| speed > max);
+        if blown_out {
+            score -= 1;
+            reasons.push(Reason::bad(Factor::Wind, "Too windy"));
+        }
+        if let Some(bearing) = self.bearing {
+            let shore = WindShore::classify(bearing, &fc.wind);
+            let acceptable = if self.profile.winds.is_empty() {
+                matches!(shore, WindShore::Offshore | WindShore::CrossOffshore)
+            } else {
+                self.profile.winds.contains(&shore)
+            };
+            if acceptable && !blown_out {
+                score += 1;
+                reasons.push(Reason::good(Factor::Wind, format!("{shore} wind")));
+            } else if !acceptable {
+                score -= 1;
+                reasons.push(Reason::bad(Factor::Wind, format!("{shore} wind")));
+            }
+        }
+
+        if let Some(level) = self.tide_level(fc.timestamp) {
+            if self.profile.tides.is_empty() {
+                // No preference
+            } else if self.profile.tides.contains(&level) {
+                reasons.push(Reason::good(Factor::Tide, format!("{level:?} tide")));
+            } else {
+                score -= 1;
+                reasons.push(Reason::bad(Factor::Tide, format!("{level:?} tide")));
+            }
+        }
+
+        Rating::new(score.clamp(0, MAX_STARS as i32) as u8, reasons)
+    }
+
+    /// Tide level at the given unix timestamp, relative to the surrounding low
+    /// and high tides
+    fn tide_level(&self, timestamp: i64) -> Option<TideLevel> {
+        let station = self.tides.as_ref()?;
+        let (start, end) = (
+            timestamp - TIDE_SEARCH_WINDOW,
+            timestamp + TIDE_SEARCH_WINDOW,
+        );
+        let predicted;
+        let extremes = match &self.extremes {
+            Some((from, until, extremes)) if *from <= start && end <= *until => extremes,
+            _ => {
+                predicted = station.extremes(start, end);
+                &predicted
+            }
+        };
+        let extremes = extremes
+            .iter()
+            .filter(|e| (start..=end).contains(&e.timestamp))
+            .collect::<Vec<_>>();
+        let low = extremes
+            .iter()
+            .filter(|e| e.kind == TideKind::Low)
+            .map(|e| e.height)
+            .reduce(f32::min)?;
+        let high = extremes
+            .iter()
+            .filter(|e| e.kind == TideKind::High)
+            .map(|e| e.height)
+            .reduce(f32::max)?;
+        if high <= low {
+            return None;
+        }
+        let proportion = (station.height(timestamp) - low) / (high - low);
+        Some(match proportion {
+            p if p < 1.0 / 3.0 => TideLevel::Low,
+            p if p < 2.0 / 3.0 => TideLevel::Mid,
+            _ => TideLevel::High,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn forecast() -> Vec<Forecast> {
+        let json = include_str!("../../test/msw/forecast.json");
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn profile() -> Profile {
+        serde_json::from_str(
+            r#"{
+                "min_height": 0.3,
+                "min_period": 8,
+                "swell_directions": ["NE", "ENE", "E", "ESE"],
+                "max_wind_speed": 20,
+                "tides": ["mid", "high"]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn too_small_is_flat() {
+        let mut fc = forecast().remove(0);
+        fc.swell.max_breaking_height = 0.0;
+        let rating = Rater::default().rate(&fc);
+        assert_eq!(rating.stars, 0);
+        assert_eq!(
+            rating.reasons,
+            vec![Reason::bad(Factor::Height, "Too small")]
+        );
+    }
+
+    #[test]
+    fn wind_relative_to_shore() {
+        let mut fc = forecast().remove(0);
+        fc.wind.speed = 5;
+        // East facing beach, with the wind travelling out to sea
+        fc.wind.direction = 90.0;
+        let rater = Rater::new(profile()).shore(90.0);
+        let offshore = rater.rate(&fc);
+        fc.wind.direction = 270.0;
+        let onshore = rater.rate(&fc);
+        assert_eq!(offshore.stars, onshore.stars + 2);
+        assert!(offshore
+            .reasons
+            .contains(&Reason::good(Factor::Wind, "offshore wind")));
+        assert!(onshore
+            .reasons
+            .contains(&Reason::bad(Factor::Wind, "onshore wind")));
+    }
+
+    #[test]
+    fn ratings_are_explained() {
+        let rater = Rater::new(profile())
+            .shore(80.0)
+            .tides(Station::builtin("8721120").unwrap());
+        for fc in forecast() {
+            let rating = rater.rate(&fc);
+            assert!(rating.stars <= MAX_STARS);
+            assert!(!rating.reasons.is_empty());
+            // Every factor with a preference in the profile is judged
+            if rating.stars > 0 {
+                for factor in [Factor::Height, Factor::Wind, Factor::Tide] {
+                    assert!(rating.reasons.iter().any(|r| r.factor == factor));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn tides_predicted_once_agree() {
+        let station = Station::builtin("8721120").unwrap();
+        let rater = Rater::new(profile()).tides(station);
+        let forecast = forecast();
+        let ranged = rater.clone().range(&forecast);
+        assert!(ranged.extremes.is_some());
+        for fc in &forecast {
+            assert_eq!(
+                ranged.tide_level(fc.timestamp),
+                rater.tide_level(fc.timestamp)
+            );
+        }
+    }
+
+    #[test]
+    fn profile_defaults_apply() {
+        let profile: Profile = serde_json::from_str(r#"{ "min_period": 10 }"#).unwrap();
+        assert_eq!(profile.min_height, Profile::default().min_height);
+        assert_eq!(profile.min_period, Some(10));
+    }
+}
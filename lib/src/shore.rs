@@ -0,0 +1,173 @@
+//! Wind and swell directions relative to the shore.
+//!
+//! A spot's bearing is the direction the beach faces, looking out to sea.
+//! Directions in the forecast follow MSW's convention of being the direction of
+//! travel, so offshore wind travels along the bearing, and swell hits the beach
+//! head on when travelling opposite to it.
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::msw::forecast::{SwellComponent, Wind};
+
+/// Wind direction relative to the shore
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WindShore {
+    Offshore,
+    CrossOffshore,
+    CrossShore,
+    CrossOnshore,
+    Onshore,
+}
+
+impl WindShore {
+    /// Classify the wind at a spot with the given beach-facing bearing
+    pub fn classify(bearing: f32, wind: &Wind) -> Self {
+        match angle_between(bearing, wind.direction) {
+            a if a <= 30.0 => Self::Offshore,
+            a if a <= 67.5 => Self::CrossOffshore,
+            a if a < 112.5 => Self::CrossShore,
+            a if a < 150.0 => Self::CrossOnshore,
+            _ => Self::Onshore,
+        }
+    }
+}
+
+impl Display for WindShore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::Offshore => f.pad("offshore"),
+            Self::CrossOffshore => f.pad("cross-off"),
+            Self::CrossShore => f.pad("cross"),
+            Self::CrossOnshore => f.pad("cross-on"),
+            Self::Onshore => f.pad("onshore"),
+        }
+    }
+}
+
+/// How exposed a spot is to a swell component
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwellExposure {
+    /// Swell approaches within 45° of straight on
+    Direct,
+    /// Swell approaches at an oblique angle, and will wrap in with less size
+    Angled,
+    /// Swell travels parallel to or away from the beach
+    Blocked,
+}
+
+impl SwellExposure {
+    /// Classify a swell component at a spot with the given beach-facing bearing
+    pub fn classify(bearing: f32, swell: &SwellComponent) -> Self {
+        // Head on swell travels opposite to the bearing
+        match angle_between(bearing + 180.0, swell.direction) {
+            a if a <= 45.0 => Self::Direct,
+            a if a < 90.0 => Self::Angled,
+            _ => Self::Blocked,
+        }
+    }
+}
+
+impl Display for SwellExposure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::Direct => f.pad("direct"),
+            Self::Angled => f.pad("angled"),
+            Self::Blocked => f.pad("blocked"),
+        }
+    }
+}
+
+/// Smallest angle between two bearings, in [0, 180]
+fn angle_between(a: f32, b: f32) -> f32 {
+    let diff = (a - b).rem_euclid(360.0);
+    diff.min(360.0 - diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msw::forecast::{CompassDirection, UnitSpeed};
+
+    fn wind(direction: f32) -> Wind {
+        Wind {
+            speed: 10,
+            direction,
+            compass_direction: CompassDirection::from_degrees(direction + 180.0),
+            chill: 20,
+            gusts: 15,
+            unit: UnitSpeed::Mph,
+        }
+    }
+
+    fn swell(direction: f32) -> SwellComponent {
+        SwellComponent {
+            height: 3.0,
+            period: 10,
+            direction,
+            compass_direction: CompassDirection::from_degrees(direction + 180.0),
+        }
+    }
+
+    #[test]
+    fn classifies_wind() {
+        // East facing beach, e.g. Florida's Atlantic coast
+        let bearing = 90.0;
+        // A west wind blows out to sea
+        assert_eq!(
+            WindShore::classify(bearing, &wind(90.0)),
+            WindShore::Offshore
+        );
+        assert_eq!(
+            WindShore::classify(bearing, &wind(45.0)),
+            WindShore::CrossOffshore
+        );
+        assert_eq!(
+            WindShore::classify(bearing, &wind(0.0)),
+            WindShore::CrossShore
+        );
+        assert_eq!(
+            WindShore::classify(bearing, &wind(220.0)),
+            WindShore::CrossOnshore
+        );
+        assert_eq!(
+            WindShore::classify(bearing, &wind(270.0)),
+            WindShore::Onshore
+        );
+    }
+
+    #[test]
+    fn classifies_across_north() {
+        // North facing beach with a south wind, travelling just east of north
+        assert_eq!(WindShore::classify(350.0, &wind(10.0)), WindShore::Offshore);
+        assert_eq!(
+            SwellExposure::classify(350.0, &swell(185.0)),
+            SwellExposure::Direct
+        );
+    }
+
+    #[test]
+    fn classifies_swell() {
+        // West facing beach, e.g. California
+        let bearing = 270.0;
+        // A west swell travels east, straight into the beach
+        assert_eq!(
+            SwellExposure::classify(bearing, &swell(90.0)),
+            SwellExposure::Direct
+        );
+        assert_eq!(
+            SwellExposure::classify(bearing, &swell(30.0)),
+            SwellExposure::Angled
+        );
+        assert_eq!(
+            SwellExposure::classify(bearing, &swell(0.0)),
+            SwellExposure::Blocked
+        );
+        assert_eq!(
+            SwellExposure::classify(bearing, &swell(270.0)),
+            SwellExposure::Blocked
+        );
+    }
+}
@@ -0,0 +1,345 @@
+//! Surf spots that forecasts can be requested for
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{Read, Write},
+    path::Path,
+};
+
+use anyhow::{bail, Context, Result};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::rating::Profile;
+use crate::tide::Station;
+
+/// Current version of the on-disk spots format
+pub const SPOTS_VERSION: u32 = 1;
+
+/// A single surf spot
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Spot {
+    /// MSW spot identifier
+    pub id: u16,
+    /// URL friendly name, e.g. `ormond-beach`
+    pub slug: String,
+    /// Display name, e.g. `Ormond Beach`, derived from the slug when missing
+    #[serde(default)]
+    pub name: String,
+    /// Location of the spot, required by providers that forecast by location
+    #[serde(flatten, default, skip_serializing_if = "Option::is_none")]
+    pub coordinates: Option<Coordinates>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// State, province, or similar, e.g. `Florida`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// IANA time zone, e.g. `America/New_York`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Direction the beach faces, i.e. looking out to sea (degrees true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bearing: Option<f32>,
+    /// Nearest tide station with harmonic constants, e.g. `8721120`
+    #[serde(
+        default,
+        alias = "tide_station",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub tide_station: Option<String>,
+    /// Conditions the spot works in, for rating forecasts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<Profile>,
+}
+
+impl Spot {
+    /// Create a spot with no metadata beyond its identifiers. The display name is
+    /// derived from the slug.
+    pub fn new(id: u16, slug: impl Into<String>) -> Self {
+        let slug = slug.into();
+        Self {
+            id,
+            name: name_from_slug(&slug),
+            slug,
+            coordinates: None,
+            country: None,
+            region: None,
+            timezone: None,
+            bearing: None,
+            tide_station: None,
+            profile: None,
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn coordinates(mut self, coordinates: Coordinates) -> Self {
+        self.coordinates = Some(coordinates);
+        self
+    }
+
+    pub fn country(mut self, country: impl Into<String>) -> Self {
+        self.country = Some(country.into());
+        self
+    }
+
+    pub fn region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    pub fn timezone(mut self, timezone: impl Into<String>) -> Self {
+        self.timezone = Some(timezone.into());
+        self
+    }
+
+    pub fn bearing(mut self, bearing: f32) -> Self {
+        self.bearing = Some(bearing);
+        self
+    }
+
+    pub fn tide_station(mut self, station: impl Into<String>) -> Self {
+        self.tide_station = Some(station.into());
+        self
+    }
+
+    pub fn profile(mut self, profile: Profile) -> Self {
+        self.profile = Some(profile);
+        self
+    }
+
+    /// Harmonic constants for the spot's tide station, if known
+    pub fn tides(&self) -> Option<Station> {
+        self.tide_station.as_deref().and_then(Station::builtin)
+    }
+
+    /// The spot's time zone, if known
+    pub fn zone(&self) -> Option<Tz> {
+        self.timezone.as_deref().and_then(|zone| zone.parse().ok())
+    }
+}
+
+/// `ormond-beach` -> `Ormond Beach`
+fn name_from_slug(slug: &str) -> String {
+    slug.split('-')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Latitude and longitude in decimal degrees
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Coordinates {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl Coordinates {
+    pub fn new(lat: f64, lon: f64) -> Self {
+        Self { lat, lon }
+    }
+}
+
+/// On-disk format of the spots data
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum SpotsFile {
+    Versioned {
+        version: u32,
+        spots: Vec<Spot>,
+    },
+    /// The original format, i.e. `{ "ormond-beach": 4203 }`
+    Legacy(HashMap<String, u16>),
+}
+
+pub struct Spots {
+    spots: HashMap<String, Spot>,
+}
+
+impl Spots {
+    /// Create a new Spots struct, pulling data from ./data/spots.json
+    pub fn new() -> Result<Self> {
+        Self::from_path("./data/spots.json")
+    }
+
+    /// Create a new Spots struct, pulling data from the given path
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = File::open(path.as_ref()).context(format!(
+            "Couldn't find spots json file at {:?}",
+            path.as_ref()
+        ))?;
+        Self::from_reader(file).context(format!(
+            "Couldn't parse file {:?} into spots json",
+            path.as_ref()
+        ))
+    }
+
+    /// Create a new Spots struct from JSON in either the versioned or the
+    /// legacy format
+    pub fn from_reader(reader: impl Read) -> Result<Self> {
+        let spots = match serde_json::from_reader(reader)? {
+            SpotsFile::Versioned { version, spots } => {
+                if version > SPOTS_VERSION {
+                    bail!("Unsupported spots file version {version}");
+                }
+                spots
+            }
+            SpotsFile::Legacy(spots) => spots
+                .into_iter()
+                .map(|(slug, id)| Spot::new(id, slug))
+                .collect(),
+        };
+        Ok(spots
+            .into_iter()
+            .map(|mut spot| {
+                if spot.name.is_empty() {
+                    spot.name = name_from_slug(&spot.slug);
+                }
+                spot
+            })
+            .collect())
+    }
+
+    /// Write the spots in the current versioned format
+    pub fn write(&self, writer: impl Write) -> Result<()> {
+        let mut spots = self.spots.values().cloned().collect::<Vec<_>>();
+        spots.sort_unstable_by(|a, b| a.slug.cmp(&b.slug));
+        let file = SpotsFile::Versioned {
+            version: SPOTS_VERSION,
+            spots,
+        };
+        serde_json::to_writer(writer, &file)?;
+        Ok(())
+    }
+
+    /// Search the spots data for the MSW spot identifier (the integer)
+    pub fn get_id<'a>(&self, name: impl Into<&'a str>) -> Option<u16> {
+        self.spots.get(name.into()).map(|spot| spot.id)
+    }
+
+    /// Look up a spot by its name, or by its MSW spot identifier
+    pub fn find(&self, name_or_id: &str) -> Option<Spot> {
+        match name_or_id.parse::<u16>() {
+            Ok(id) => Some(
+                self.spots
+                    .values()
+                    .find(|spot| spot.id == id)
+                    .cloned()
+                    .unwrap_or_else(|| Spot::new(id, name_or_id)),
+            ),
+            Err(_) => self.spots.get(name_or_id).cloned(),
+        }
+    }
+
+    /// Spots whose slug contains the given substring, or all spots, sorted by
+    /// slug
+    pub fn search(&self, substring: Option<&str>) -> Vec<Spot> {
+        let mut spots = self
+            .spots
+            .values()
+            .filter(|spot| substring.is_none_or(|s| spot.slug.contains(s)))
+            .cloned()
+            .collect::<Vec<_>>();
+        spots.sort_unstable_by(|a, b| a.slug.cmp(&b.slug));
+        spots
+    }
+
+    /// Iterate over all spots (requires cloning)
+    pub fn into_vec(&self) -> Vec<(String, u16)> {
+        self.spots
+            .iter()
+            .map(|(slug, spot)| (slug.clone(), spot.id))
+            .collect()
+    }
+}
+
+impl FromIterator<Spot> for Spots {
+    fn from_iter<I: IntoIterator<Item = Spot>>(iter: I) -> Self {
+        let spots = iter
+            .into_iter()
+            .map(|spot| (spot.slug.clone(), spot))
+            .collect();
+        Self { spots }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_versioned_spots() {
+        let spots = Spots::from_path("../test/spots.json").unwrap();
+        let ormond = spots.find("ormond-beach").unwrap();
+        assert_eq!(ormond.id, 4203);
+        assert_eq!(ormond.name, "Ormond Beach");
+        assert_eq!(
+            ormond.coordinates,
+            Some(Coordinates::new(29.2858, -81.0559))
+        );
+        assert_eq!(ormond.region.as_deref(), Some("Florida"));
+        assert_eq!(ormond.timezone.as_deref(), Some("America/New_York"));
+        assert_eq!(ormond.bearing, Some(80.0));
+        assert!(ormond.tides().is_some());
+        assert_eq!(ormond.profile.as_ref().unwrap().min_period, Some(8));
+        assert_eq!(spots.find("4203"), Some(ormond));
+
+        // Metadata is optional
+        let pipeline = spots.find("pipeline").unwrap();
+        assert_eq!(pipeline, Spot::new(616, "pipeline"));
+    }
+
+    #[test]
+    fn loads_legacy_spots() {
+        let json = r#"{ "ormond-beach": 4203, "mavericks-half-moon-bay": 162 }"#;
+        let spots = Spots::from_reader(json.as_bytes()).unwrap();
+        let mavericks = spots.find("mavericks-half-moon-bay").unwrap();
+        assert_eq!(mavericks.id, 162);
+        assert_eq!(mavericks.name, "Mavericks Half Moon Bay");
+        assert_eq!(mavericks.coordinates, None);
+    }
+
+    #[test]
+    fn loads_legacy_spots_file() {
+        let spots = Spots::from_path("../test/spots-legacy.json").unwrap();
+        let ormond = spots.find("ormond-beach").unwrap();
+        assert_eq!(ormond.id, 4203);
+        assert_eq!(ormond.name, "Ormond Beach");
+    }
+
+    #[test]
+    fn defaults_missing_names() {
+        let json = r#"{ "version": 1, "spots": [{ "id": 616, "slug": "pipeline" }] }"#;
+        let spots = Spots::from_reader(json.as_bytes()).unwrap();
+        assert_eq!(spots.find("pipeline").unwrap().name, "Pipeline");
+    }
+
+    #[test]
+    fn round_trips_through_current_version() {
+        let spots = Spots::from_path("../test/spots.json").unwrap();
+        let mut buffer = Vec::new();
+        spots.write(&mut buffer).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(json["version"], SPOTS_VERSION);
+        let reloaded = Spots::from_reader(buffer.as_slice()).unwrap();
+        assert_eq!(reloaded.find("ormond-beach"), spots.find("ormond-beach"));
+    }
+
+    #[test]
+    fn rejects_future_versions() {
+        let json = r#"{ "version": 99, "spots": [] }"#;
+        assert!(Spots::from_reader(json.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,147 @@
+//! Sunrise, sunset, and civil twilight times.
+//!
+//! Uses the [sunrise equation](https://en.wikipedia.org/wiki/Sunrise_equation),
+//! which is accurate to within a minute or two at surfable latitudes.
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::spot::Coordinates;
+
+/// Julian date of J2000.0 (2000-01-01 12:00 UTC)
+const J2000: f64 = 2_451_545.0;
+
+/// Julian date of the unix epoch
+const UNIX_EPOCH_JULIAN: f64 = 2_440_587.5;
+
+/// Obliquity of the ecliptic (degrees)
+const OBLIQUITY: f64 = 23.4397;
+
+/// Solar elevation at sunrise/sunset, accounting for refraction and the
+/// sun's radius (degrees)
+const SUNRISE_ELEVATION: f64 = -0.833;
+
+/// Solar elevation at the start of civil dawn and end of civil dusk (degrees)
+const CIVIL_TWILIGHT_ELEVATION: f64 = -6.0;
+
+/// Daylight times on a given date, as unix timestamps (UTC). Times are `None`
+/// when they don't occur, e.g. during polar night or the midnight sun.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SunTimes {
+    /// First light
+    pub civil_dawn: Option<i64>,
+    pub sunrise: Option<i64>,
+    pub sunset: Option<i64>,
+    /// Last light
+    pub civil_dusk: Option<i64>,
+    /// Whether the sun stays below civil twilight all day
+    polar_night: bool,
+}
+
+impl SunTimes {
+    /// Compute the daylight times at a location on the given (local) date
+    pub fn new(coordinates: Coordinates, date: NaiveDate) -> Self {
+        let days = date.num_days_from_ce() - NaiveDate::from_ymd(2000, 1, 1).num_days_from_ce();
+        // Mean solar time at the location
+        let mean_solar = days as f64 - coordinates.lon / 360.0;
+        let anomaly = (357.5291 + 0.985_600_28 * mean_solar).rem_euclid(360.0);
+        let m = anomaly.to_radians();
+        let center = 1.9148 * m.sin() + 0.02 * (2.0 * m).sin() + 0.0003 * (3.0 * m).sin();
+        let ecliptic_longitude = (anomaly + center + 180.0 + 102.9372)
+            .rem_euclid(360.0)
+            .to_radians();
+        let transit =
+            J2000 + mean_solar + 0.0053 * m.sin() - 0.0069 * (2.0 * ecliptic_longitude).sin();
+        let declination = (ecliptic_longitude.sin() * OBLIQUITY.to_radians().sin()).asin();
+
+        let latitude = coordinates.lat.to_radians();
+        // Returns the (morning, evening) times the sun crosses the elevation
+        let crossings = |elevation: f64| {
+            let cos_hour_angle = (elevation.to_radians().sin()
+                - latitude.sin() * declination.sin())
+                / (latitude.cos() * declination.cos());
+            if !(-1.0..=1.0).contains(&cos_hour_angle) {
+                return (None, None);
+            }
+            let offset = cos_hour_angle.acos().to_degrees() / 360.0;
+            (
+                Some(julian_to_unix(transit - offset)),
+                Some(julian_to_unix(transit + offset)),
+            )
+        };
+
+        let (sunrise, sunset) = crossings(SUNRISE_ELEVATION);
+        let (civil_dawn, civil_dusk) = crossings(CIVIL_TWILIGHT_ELEVATION);
+        // When twilight doesn't occur, the sun is either always above or always
+        // below it; check which at solar noon.
+        let noon_elevation = 90.0 - (coordinates.lat - declination.to_degrees()).abs();
+        Self {
+            civil_dawn,
+            sunrise,
+            sunset,
+            civil_dusk,
+            polar_night: noon_elevation < CIVIL_TWILIGHT_ELEVATION,
+        }
+    }
+
+    /// Whether it is dark (before first light or after last light) at the given
+    /// unix timestamp
+    pub fn is_dark(&self, timestamp: i64) -> bool {
+        match (self.civil_dawn, self.civil_dusk) {
+            (Some(dawn), Some(dusk)) => timestamp < dawn || timestamp > dusk,
+            _ => self.polar_night,
+        }
+    }
+}
+
+fn julian_to_unix(julian: f64) -> i64 {
+    ((julian - UNIX_EPOCH_JULIAN) * 86400.0).round() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+
+    use super::*;
+
+    fn utc(timestamp: Option<i64>) -> String {
+        NaiveDateTime::from_timestamp(timestamp.unwrap(), 0)
+            .format("%H:%M")
+            .to_string()
+    }
+
+    #[test]
+    fn london_summer_solstice() {
+        let london = Coordinates::new(51.5074, -0.1278);
+        let times = SunTimes::new(london, NaiveDate::from_ymd(2024, 6, 21));
+        // Published times are 04:43 and 21:21 BST
+        let close_to = |actual: Option<i64>, expected: &str| {
+            let expected =
+                NaiveDateTime::parse_from_str(&format!("2024-06-21 {expected}"), "%Y-%m-%d %H:%M")
+                    .unwrap()
+                    .timestamp();
+            assert!(
+                (actual.unwrap() - expected).abs() <= 120,
+                "{} != {expected}",
+                utc(actual)
+            );
+        };
+        close_to(times.sunrise, "03:43");
+        close_to(times.sunset, "20:21");
+        assert!(times.civil_dawn < times.sunrise);
+        assert!(times.civil_dusk > times.sunset);
+        assert!(times.is_dark(times.civil_dawn.unwrap() - 60));
+        assert!(!times.is_dark(times.sunrise.unwrap()));
+        assert!(times.is_dark(times.civil_dusk.unwrap() + 60));
+    }
+
+    #[test]
+    fn polar_day_and_night() {
+        let svalbard = Coordinates::new(78.22, 15.65);
+        let summer = SunTimes::new(svalbard, NaiveDate::from_ymd(2024, 6, 21));
+        assert_eq!(summer.sunrise, None);
+        assert!(!summer.is_dark(1718971200));
+        let winter = SunTimes::new(svalbard, NaiveDate::from_ymd(2024, 12, 21));
+        assert_eq!(winter.sunset, None);
+        assert!(winter.is_dark(1734782400));
+    }
+}
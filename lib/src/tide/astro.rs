@@ -0,0 +1,159 @@
+//! Astronomical arguments and nodal corrections for the tidal constituents,
+//! following Schureman's conventions. Equilibrium arguments are computed
+//! directly from the mean longitudes at the requested time, so phases must be
+//! relative to Greenwich (NOAA's "Phase GMT").
+
+/// Days between the unix epoch and J2000.0 (2000-01-01 12:00 UTC)
+const J2000_UNIX_DAYS: f64 = 10957.5;
+
+/// Mean longitudes (degrees) at a point in time
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Longitudes {
+    /// Hour angle of the mean sun
+    t: f64,
+    /// Moon
+    s: f64,
+    /// Sun
+    h: f64,
+    /// Lunar perigee
+    p: f64,
+    /// Moon's ascending node
+    n: f64,
+    /// Solar perigee
+    p1: f64,
+}
+
+impl Longitudes {
+    pub(super) fn at(timestamp: i64) -> Self {
+        let days = timestamp as f64 / 86400.0 - J2000_UNIX_DAYS;
+        let centuries = days / 36525.0;
+        let hours = (timestamp.rem_euclid(86400)) as f64 / 3600.0;
+        Self {
+            t: 180.0 + 15.0 * hours,
+            s: 218.316_459_1 + 481_267.881_342_36 * centuries,
+            h: 280.466_456_7 + 36_000.769_827_79 * centuries,
+            p: 83.353_243 + 4_069.013_711_1 * centuries,
+            n: 125.044_555 - 1_934.136_184_9 * centuries,
+            p1: 282.937_3 + 1.719_5 * centuries,
+        }
+    }
+}
+
+/// How a constituent's amplitude and phase vary over the 18.6 year nodal cycle
+#[derive(Clone, Copy, Debug)]
+enum Nodal {
+    None,
+    M2,
+    K1,
+    O1,
+    K2,
+    J1,
+    Mf,
+    Mm,
+    /// Compound of the M2 correction, e.g. M4 = M2²
+    M2Power(i32),
+}
+
+impl Nodal {
+    /// Amplitude factor `f` and phase correction `u` (degrees)
+    fn correction(self, n: f64) -> (f64, f64) {
+        let n = n.to_radians();
+        let (c1, c2, c3) = (n.cos(), (2.0 * n).cos(), (3.0 * n).cos());
+        let (s1, s2, s3) = (n.sin(), (2.0 * n).sin(), (3.0 * n).sin());
+        match self {
+            Nodal::None => (1.0, 0.0),
+            Nodal::M2 => (1.0004 - 0.0373 * c1 + 0.0002 * c2, -2.14 * s1),
+            Nodal::K1 => (
+                1.0060 + 0.1150 * c1 - 0.0088 * c2 + 0.0006 * c3,
+                -8.86 * s1 + 0.68 * s2 - 0.07 * s3,
+            ),
+            Nodal::O1 => (
+                1.0089 + 0.1871 * c1 - 0.0147 * c2 + 0.0014 * c3,
+                10.80 * s1 - 1.34 * s2 + 0.19 * s3,
+            ),
+            Nodal::K2 => (
+                1.0241 + 0.2863 * c1 + 0.0083 * c2 - 0.0015 * c3,
+                -17.74 * s1 + 0.68 * s2 - 0.04 * s3,
+            ),
+            Nodal::J1 => (
+                1.0129 + 0.1676 * c1 - 0.0170 * c2 + 0.0016 * c3,
+                -12.94 * s1 + 1.34 * s2 - 0.19 * s3,
+            ),
+            Nodal::Mf => (
+                1.0429 + 0.4135 * c1 - 0.004 * c2,
+                -23.74 * s1 + 2.68 * s2 - 0.38 * s3,
+            ),
+            Nodal::Mm => (1.0 - 0.1300 * c1 + 0.0013 * c2, 0.0),
+            Nodal::M2Power(k) => {
+                let (f, u) = Nodal::M2.correction(n.to_degrees());
+                (f.powi(k), u * k as f64)
+            }
+        }
+    }
+}
+
+/// Multiples of (T, s, h, p, N, p1), a fixed phase offset, and the nodal
+/// correction for a constituent.
+struct Definition {
+    name: &'static str,
+    coefficients: [f64; 6],
+    offset: f64,
+    nodal: Nodal,
+}
+
+const fn def(name: &'static str, coefficients: [f64; 6], offset: f64, nodal: Nodal) -> Definition {
+    Definition {
+        name,
+        coefficients,
+        offset,
+        nodal,
+    }
+}
+
+#[rustfmt::skip]
+const CONSTITUENTS: [Definition; 24] = [
+    // Semidiurnal
+    def("M2",   [2.0, -2.0,  2.0,  0.0, 0.0, 0.0],   0.0, Nodal::M2),
+    def("S2",   [2.0,  0.0,  0.0,  0.0, 0.0, 0.0],   0.0, Nodal::None),
+    def("N2",   [2.0, -3.0,  2.0,  1.0, 0.0, 0.0],   0.0, Nodal::M2),
+    def("K2",   [2.0,  0.0,  2.0,  0.0, 0.0, 0.0],   0.0, Nodal::K2),
+    def("2N2",  [2.0, -4.0,  2.0,  2.0, 0.0, 0.0],   0.0, Nodal::M2),
+    def("NU2",  [2.0, -3.0,  4.0, -1.0, 0.0, 0.0],   0.0, Nodal::M2),
+    def("MU2",  [2.0, -4.0,  4.0,  0.0, 0.0, 0.0],   0.0, Nodal::M2),
+    def("L2",   [2.0, -1.0,  2.0, -1.0, 0.0, 0.0], 180.0, Nodal::M2),
+    def("T2",   [2.0,  0.0, -1.0,  0.0, 0.0, 1.0],   0.0, Nodal::None),
+    // Diurnal
+    def("K1",   [1.0,  0.0,  1.0,  0.0, 0.0, 0.0], -90.0, Nodal::K1),
+    def("O1",   [1.0, -2.0,  1.0,  0.0, 0.0, 0.0],  90.0, Nodal::O1),
+    def("P1",   [1.0,  0.0, -1.0,  0.0, 0.0, 0.0],  90.0, Nodal::None),
+    def("Q1",   [1.0, -3.0,  1.0,  1.0, 0.0, 0.0],  90.0, Nodal::O1),
+    def("J1",   [1.0,  1.0,  1.0, -1.0, 0.0, 0.0], -90.0, Nodal::J1),
+    def("S1",   [1.0,  0.0,  0.0,  0.0, 0.0, 0.0],   0.0, Nodal::None),
+    // Shallow water
+    def("M4",   [4.0, -4.0,  4.0,  0.0, 0.0, 0.0],   0.0, Nodal::M2Power(2)),
+    def("MN4",  [4.0, -5.0,  4.0,  1.0, 0.0, 0.0],   0.0, Nodal::M2Power(2)),
+    def("MS4",  [4.0, -2.0,  2.0,  0.0, 0.0, 0.0],   0.0, Nodal::M2),
+    def("M6",   [6.0, -6.0,  6.0,  0.0, 0.0, 0.0],   0.0, Nodal::M2Power(3)),
+    def("S4",   [4.0,  0.0,  0.0,  0.0, 0.0, 0.0],   0.0, Nodal::None),
+    // Long period
+    def("SA",   [0.0,  0.0,  1.0,  0.0, 0.0, 0.0],   0.0, Nodal::None),
+    def("SSA",  [0.0,  0.0,  2.0,  0.0, 0.0, 0.0],   0.0, Nodal::None),
+    def("MM",   [0.0,  1.0,  0.0, -1.0, 0.0, 0.0],   0.0, Nodal::Mm),
+    def("MF",   [0.0,  2.0,  0.0,  0.0, 0.0, 0.0],   0.0, Nodal::Mf),
+];
+
+/// Index of a supported constituent by its NOAA name, e.g. `M2`
+pub(super) fn lookup(name: &str) -> Option<usize> {
+    CONSTITUENTS
+        .iter()
+        .position(|c| c.name.eq_ignore_ascii_case(name))
+}
+
+/// The node factor `f` and the argument `V + u` (degrees) of a constituent
+pub(super) fn argument(ix: usize, at: &Longitudes) -> (f64, f64) {
+    let c = &CONSTITUENTS[ix];
+    let [kt, ks, kh, kp, kn, kp1] = c.coefficients;
+    let v = kt * at.t + ks * at.s + kh * at.h + kp * at.p + kn * at.n + kp1 * at.p1 + c.offset;
+    let (f, u) = c.nodal.correction(at.n);
+    (f, v + u)
+}
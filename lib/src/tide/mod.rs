@@ -0,0 +1,243 @@
+//! Offline tide predictions from harmonic constituents.
+//!
+//! Each station has a table of constituent amplitudes and Greenwich phases, in
+//! the same shape NOAA CO-OPS publishes them. The predicted height is the sum
+//! of the constituents' cosines, offset by mean sea level above the datum.
+
+mod astro;
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+use crate::msw::forecast::UnitLength;
+use astro::Longitudes;
+
+/// Seconds between samples when searching for high and low tides
+const SEARCH_STEP: i64 = 6 * 60;
+
+/// Harmonic constants for a tide station
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "StationTable")]
+pub struct Station {
+    pub id: String,
+    pub name: String,
+    pub unit: UnitLength,
+    /// Mean sea level above the station datum
+    msl: f64,
+    constituents: Vec<Constituent>,
+}
+
+#[derive(Clone, Debug)]
+struct Constituent {
+    /// Index into the supported constituents
+    ix: usize,
+    amplitude: f64,
+    /// Greenwich phase lag (degrees)
+    phase: f64,
+}
+
+/// On-disk format of a station's harmonic constants
+#[derive(Deserialize)]
+struct StationTable {
+    id: String,
+    name: String,
+    unit: UnitLength,
+    msl: f64,
+    constituents: Vec<ConstituentRow>,
+}
+
+#[derive(Deserialize)]
+struct ConstituentRow {
+    name: String,
+    amplitude: f64,
+    phase: f64,
+}
+
+impl TryFrom<StationTable> for Station {
+    type Error = anyhow::Error;
+
+    fn try_from(table: StationTable) -> Result<Self> {
+        let constituents = table
+            .constituents
+            .into_iter()
+            .map(|row| {
+                let ix = astro::lookup(&row.name)
+                    .ok_or_else(|| anyhow!("Unsupported tidal constituent {}", row.name))?;
+                Ok(Constituent {
+                    ix,
+                    amplitude: row.amplitude,
+                    phase: row.phase,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            id: table.id,
+            name: table.name,
+            unit: table.unit,
+            msl: table.msl,
+            constituents,
+        })
+    }
+}
+
+/// Whether a tide extreme is a high or a low
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TideKind {
+    High,
+    Low,
+}
+
+/// A high or low tide
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TideExtreme {
+    /// Unix timestamp (UTC)
+    pub timestamp: i64,
+    pub height: f32,
+    pub kind: TideKind,
+}
+
+impl Station {
+    /// Stations whose harmonic constants ship with this library
+    pub fn builtin(id: &str) -> Option<Self> {
+        let json = match id {
+            "8721120" => include_str!("stations/8721120.json"),
+            "9414290" => include_str!("stations/9414290.json"),
+            _ => return None,
+        };
+        Some(serde_json::from_str(json).expect("builtin station tables are valid"))
+    }
+
+    /// Convert the station's heights to the given unit
+    pub fn in_unit(mut self, unit: UnitLength) -> Self {
+        let factor = match (self.unit, unit) {
+            (UnitLength::Feet, UnitLength::Meters) => 0.3048,
+            (UnitLength::Meters, UnitLength::Feet) => 1.0 / 0.3048,
+            _ => 1.0,
+        };
+        self.msl *= factor;
+        self.constituents
+            .iter_mut()
+            .for_each(|c| c.amplitude *= factor);
+        self.unit = unit;
+        self
+    }
+
+    /// Predicted height above the station datum at the given unix timestamp
+    pub fn height(&self, timestamp: i64) -> f32 {
+        let longitudes = Longitudes::at(timestamp);
+        let height = self.constituents.iter().fold(self.msl, |acc, c| {
+            let (f, argument) = astro::argument(c.ix, &longitudes);
+            acc + f * c.amplitude * (argument - c.phase).to_radians().cos()
+        });
+        height as f32
+    }
+
+    /// Whether the tide is rising at the given unix timestamp
+    pub fn is_rising(&self, timestamp: i64) -> bool {
+        self.height(timestamp + 60) > self.height(timestamp)
+    }
+
+    /// High and low tides between the given unix timestamps, to the minute
+    pub fn extremes(&self, start: i64, end: i64) -> Vec<TideExtreme> {
+        let mut extremes = Vec::new();
+        let mut t = start;
+        let mut rising = self.is_rising(t);
+        while t < end {
+            let next = (t + SEARCH_STEP).min(end);
+            let next_rising = self.is_rising(next);
+            if next_rising != rising {
+                // Turning point is within this step; find it to the minute
+                let timestamp = (t..=next)
+                    .step_by(60)
+                    .find(|m| self.is_rising(*m) == next_rising)
+                    .unwrap_or(next);
+                extremes.push(TideExtreme {
+                    timestamp,
+                    height: self.height(timestamp),
+                    kind: if rising {
+                        TideKind::High
+                    } else {
+                        TideKind::Low
+                    },
+                });
+            }
+            rising = next_rising;
+            t = next;
+        }
+        extremes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(constituents: &str) -> Station {
+        let json = format!(
+            r#"{{"id": "0", "name": "Test", "unit": "m", "msl": 1.0, "constituents": {constituents}}}"#
+        );
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn solar_constituent_follows_the_clock() {
+        // S2 peaks at noon and midnight Greenwich time, with zero phase lag
+        let station = table(r#"[{"name": "S2", "amplitude": 2.0, "phase": 0.0}]"#);
+        let midnight = 1656979200; // 2022-07-05 00:00:00 UTC
+        assert!((station.height(midnight) - 3.0).abs() < 1e-4);
+        assert!((station.height(midnight + 3 * 3600) - 1.0).abs() < 1e-4);
+        assert!((station.height(midnight + 6 * 3600) + 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn lunar_constituent_has_lunar_period() {
+        let station = table(r#"[{"name": "M2", "amplitude": 1.0, "phase": 45.0}]"#);
+        let start = 1656979200;
+        // Period of M2 is ~12.42 hours
+        let period = (12.420_601 * 3600.0) as i64;
+        for offset in [0, 1000, 20000] {
+            let t = start + offset;
+            assert!((station.height(t) - station.height(t + period)).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_constituents() {
+        let json = r#"{"id": "0", "name": "Test", "unit": "m", "msl": 1.0,
+            "constituents": [{"name": "XX9", "amplitude": 1.0, "phase": 0.0}]}"#;
+        assert!(serde_json::from_str::<Station>(json).is_err());
+    }
+
+    #[test]
+    fn extremes_alternate() {
+        let station = Station::builtin("8721120").unwrap();
+        let start = 1656979200;
+        let extremes = station.extremes(start, start + 2 * 86400);
+        // Semidiurnal station: two highs and two lows per (lunar) day
+        assert!((7..=9).contains(&extremes.len()), "{extremes:?}");
+        for pair in extremes.windows(2) {
+            assert_ne!(pair[0].kind, pair[1].kind);
+            match pair[0].kind {
+                TideKind::High => assert!(pair[0].height > pair[1].height),
+                TideKind::Low => assert!(pair[0].height < pair[1].height),
+            }
+        }
+        for extreme in extremes {
+            let height = station.height(extreme.timestamp);
+            let around =
+                [extreme.timestamp - 600, extreme.timestamp + 600].map(|t| station.height(t));
+            match extreme.kind {
+                TideKind::High => assert!(around.iter().all(|h| *h <= height)),
+                TideKind::Low => assert!(around.iter().all(|h| *h >= height)),
+            }
+        }
+    }
+
+    #[test]
+    fn converts_units() {
+        let station = Station::builtin("9414290").unwrap();
+        let feet = station.clone().in_unit(UnitLength::Feet);
+        let t = 1656979200;
+        assert!((feet.height(t) * 0.3048 - station.height(t)).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,74 @@
+//! Rendering logic for 7-bit ASCII, e.g. serial consoles, email and old
+//! Windows terminals
+
+use unicode_normalization::char::decompose_canonical;
+
+use super::plain::Plain;
+use super::render::Render;
+use super::view::{layout::Charset, View};
+
+/// Renders the text of a view with ASCII characters only. Views are laid out
+/// with letter compass directions, and the box drawing, symbols and accented
+/// letters left over are swapped one for one, so columns stay aligned.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Ascii;
+
+impl Render for Ascii {
+    type Output = String;
+
+    fn render<V: Into<View>>(&self, view: V) -> Self::Output {
+        Plain.render(view).chars().map(to_ascii).collect()
+    }
+
+    fn charset(&self) -> Charset {
+        Charset::Ascii
+    }
+}
+
+fn to_ascii(c: char) -> char {
+    match c {
+        _ if c.is_ascii() => c,
+        '─' | '═' => '-',
+        '│' | '║' => '|',
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' | '╔' | '╗' | '╚' | '╝' => '+',
+        '★' => '*',
+        '☆' => '.',
+        '↑' => '^',
+        '↓' => 'v',
+        '←' => '<',
+        '→' => '>',
+        '↗' | '↙' => '/',
+        '↖' | '↘' => '\\',
+        '…' | '·' => '.',
+        '█' => '#',
+        '°' => ' ',
+        _ => base_letter(c).unwrap_or('?'),
+    }
+}
+
+/// The letter an accented one is built on, e.g. `e` for `é`
+fn base_letter(c: char) -> Option<char> {
+    let mut base = None;
+    decompose_canonical(c, |part| {
+        base.get_or_insert(part);
+    });
+    base.filter(char::is_ascii_alphabetic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::view::Span;
+
+    #[test]
+    fn swaps_symbols_in_place() {
+        let view = View {
+            spans: vec![Span::new("┌─ ★☆ ↗ Côte Señora ─┐")],
+        };
+        assert_eq!(Ascii.render(view), "+- *. / Cote Senora -+");
+        let view = View {
+            spans: vec![Span::new("Łódź 東京")],
+        };
+        assert_eq!(Ascii.render(view), "?odz ??");
+    }
+}
@@ -1,55 +1,121 @@
 //! Rendering logic for browsers
 
 use super::render::Render;
-use super::view::{Color, Content, View};
+use super::theme::Theme;
+use super::view::{Color, Content, Role, Style, View};
+use crate::rating::Level;
 
-pub struct Browser;
+/// Renders HTML, with the theme's colors
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Browser {
+    theme: Theme,
+}
+
+impl Browser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
 
 impl Render for Browser {
     type Output = String;
 
-    fn render<V: Into<View>>(view: V) -> Self::Output {
+    fn render<V: Into<View>>(&self, view: V) -> Self::Output {
         let mut output = String::new();
+        let (background, foreground) = self.theme.page();
         // insert preamble
-        output.push_str(
+        output.push_str(&format!(
             r#"<html>
                 <head>
                     <link rel="stylesheet" href="https://fonts.googleapis.com/css?family=Fira+Code">
                     <style type="text/css">
-                        body {
-                            background: #282828;
-                            color: #ebdbb2;
-                        }
-                        pre {
+                        body {{
+                            background: {background};
+                            color: {foreground};
+                        }}
+                        pre {{
                             font-family: "Fira Code", "Courier New", "DejaVu Sans Mono", "Lucida Console", monospace;
-                        }
-                        .bold {
+                        }}
+                        .bold {{
                             font-weight: bold;
-                        }
-                        .red {
-                            color: #cc241d;
-                        }
-                        .blue {
-                            color: #bbbbbb;
-                        }
-                        .green {
-                            color: #98971a;
-                        }
-                    </style>
+                        }}
+"#,
+        ));
+        // Roles come first, so that explicit colors take precedence
+        for role in ROLES {
+            let rules = self.rules(self.theme.style(role));
+            if !rules.is_empty() {
+                output.push_str(&format!(
+                    "                        {} {{ {} }}\n",
+                    selector(role),
+                    rules.join(" ")
+                ));
+            }
+        }
+        for color in Color::NAMED {
+            let class = class(color).unwrap();
+            let value = self.theme.css(color);
+            output.push_str(&format!(
+                "                        .{class} {{ color: {value}; }}\n                        .bg-{class} {{ background: {value}; }}\n"
+            ));
+        }
+        output.push_str(
+            r#"                    </style>
                 </head>
                 <body><pre>"#,
         );
-        for span in view.into().spans {
-            if let Some(color) = span.style.fg {
-                output.push_str("<span class=\"");
-                output.push_str(color_to_str(color));
-                output.push_str("\">");
+        for span in view.into().into_text() {
+            let style = span.style;
+            let mut classes = vec![];
+            let mut inline = vec![];
+            let mut data = vec![];
+            if let Some(role) = style.role {
+                classes.push(role_class(role).to_owned());
+                if let Role::Rating(level) = role {
+                    data.push(format!("data-rating=\"{}\"", level.name()));
+                }
+            }
+            if let Some(color) = style.fg {
+                match class(color) {
+                    Some(class) => classes.push(class.to_owned()),
+                    None => inline.push(format!("color: {}", self.theme.css(color))),
+                }
+            }
+            if style.bold {
+                classes.push("bold".to_owned());
+            }
+            if let Some(color) = style.bg {
+                match class(color) {
+                    Some(class) => classes.push(format!("bg-{class}")),
+                    None => inline.push(format!("background: {}", self.theme.css(color))),
+                }
+            }
+            let styled = !classes.is_empty() || !inline.is_empty();
+            if styled {
+                output.push_str("<span");
+                if !classes.is_empty() {
+                    output.push_str(&format!(" class=\"{}\"", classes.join(" ")));
+                }
+                for attribute in &data {
+                    output.push_str(&format!(" {attribute}"));
+                }
+                if !inline.is_empty() {
+                    output.push_str(&format!(" style=\"{}\"", inline.join("; ")));
+                }
+                output.push('>');
             }
             match span.content {
                 Content::Text(text) => output.push_str(text.as_str()),
                 Content::Newline => output.push('\n'),
+                // Already replaced by their text
+                Content::Chart(_) | Content::Table(_) => {}
             }
-            if span.style.fg.is_some() {
+            if styled {
                 output.push_str("</span>");
             }
         }
@@ -67,10 +133,111 @@ impl Render for Browser {
     }
 }
 
-fn color_to_str(color: Color) -> &'static str {
+impl Browser {
+    /// CSS declarations for a style
+    fn rules(&self, style: Style) -> Vec<String> {
+        let mut rules = vec![];
+        if let Some(color) = style.fg {
+            rules.push(format!("color: {};", self.theme.css(color)));
+        }
+        if let Some(color) = style.bg {
+            rules.push(format!("background: {};", self.theme.css(color)));
+        }
+        if style.bold {
+            rules.push("font-weight: bold;".to_owned());
+        }
+        rules
+    }
+}
+
+const ROLES: [Role; 9] = [
+    Role::Rating(Level::Good),
+    Role::Rating(Level::Fair),
+    Role::Rating(Level::Poor),
+    Role::Night,
+    Role::Axis,
+    Role::Legend,
+    Role::Border,
+    Role::Warning,
+    Role::Muted,
+];
+
+/// CSS class of a role. Ratings share a class, and carry their level in a
+/// `data-rating` attribute.
+fn role_class(role: Role) -> &'static str {
+    match role {
+        Role::Rating(_) => "rating",
+        Role::Night => "night",
+        Role::Axis => "axis",
+        Role::Legend => "legend",
+        Role::Border => "border",
+        Role::Warning => "warning",
+        Role::Muted => "muted",
+    }
+}
+
+/// CSS selector for spans with the role
+fn selector(role: Role) -> String {
+    match role {
+        Role::Rating(level) => format!("[data-rating=\"{}\"]", level.name()),
+        _ => format!(".{}", role_class(role)),
+    }
+}
+
+/// CSS class of a named color
+fn class(color: Color) -> Option<&'static str> {
     match color {
-        Color::Red => "red",
-        Color::Green => "green",
-        Color::Blue => "blue",
+        Color::Black => Some("black"),
+        Color::Red => Some("red"),
+        Color::Green => Some("green"),
+        Color::Yellow => Some("yellow"),
+        Color::Blue => Some("blue"),
+        Color::Magenta => Some("magenta"),
+        Color::Cyan => Some("cyan"),
+        Color::White => Some("white"),
+        Color::Gray => Some("gray"),
+        Color::Fixed(_) | Color::Rgb(..) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::view::Span;
+
+    #[test]
+    fn composes_classes() {
+        let mut span = Span::new("hi");
+        span.style().fg(Color::Red).bold().bg(Color::Blue);
+        let mut custom = Span::new("there");
+        custom
+            .style()
+            .fg(Color::Rgb(255, 128, 0))
+            .bg(Color::Fixed(16));
+        let html = Browser::new().render(View {
+            spans: vec![span, custom],
+        });
+        assert!(html.contains(r#"<span class="red bold bg-blue">hi</span>"#));
+        assert!(html.contains(r#"<span style="color: #ff8000; background: #000000">there</span>"#));
+        assert!(html.contains(".bg-blue { background: #bbbbbb; }"));
+    }
+
+    #[test]
+    fn styles_roles_per_theme() {
+        let mut span = Span::new("hi");
+        span.style().role(Role::Rating(Level::Good));
+        let mut warning = Span::new("oops");
+        warning.style().role(Role::Warning);
+        let view = || View {
+            spans: vec![span.clone(), warning.clone()],
+        };
+        let html = Browser::new().render(view());
+        assert!(html.contains(r#"<span class="rating" data-rating="good">hi</span>"#));
+        assert!(html.contains(r#"[data-rating="good"] { color: #98971a; }"#));
+        assert!(html.contains(r#"<span class="warning">oops</span>"#));
+        assert!(html.contains(".warning { color: #cc241d; }"));
+        let html = Browser::new().theme(Theme::HighContrast).render(view());
+        assert!(html.contains(r#"[data-rating="good"] { color: #55ff55; font-weight: bold; }"#));
+        assert!(html.contains("background: #000000;"));
     }
 }
@@ -0,0 +1,107 @@
+//! Rendering logic for Markdown, e.g. to paste into issues, Notion and Discord
+
+use super::plain::Plain;
+use super::render::Render;
+use super::view::table::Table;
+use super::view::{Content, Span, View};
+
+/// Renders GitHub-flavored Markdown: tables as Markdown tables, and the rest
+/// as its text in fenced code blocks, to keep the alignment of graphs
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Markdown;
+
+impl Render for Markdown {
+    type Output = String;
+
+    fn render<V: Into<View>>(&self, view: V) -> Self::Output {
+        let mut blocks = vec![];
+        let mut text = vec![];
+        for span in view.into().spans {
+            match span.content {
+                Content::Text(_) | Content::Newline => text.push(span),
+                Content::Chart(chart) => {
+                    blocks.extend(fence(std::mem::take(&mut text)));
+                    blocks.extend(fence(chart.text));
+                }
+                Content::Table(table) => {
+                    blocks.extend(fence(std::mem::take(&mut text)));
+                    blocks.push(table_block(&table));
+                }
+            }
+        }
+        blocks.extend(fence(text));
+        blocks.join("\n")
+    }
+}
+
+/// Text in a fenced code block, unless there's nothing to show
+fn fence(spans: Vec<Span>) -> Option<String> {
+    let text = Plain.render(View { spans });
+    let text = text.trim_matches('\n');
+    if text.trim().is_empty() {
+        return None;
+    }
+    Some(format!("```\n{text}\n```\n"))
+}
+
+/// A heading, the table, and its notes as paragraphs
+fn table_block(table: &Table) -> String {
+    let mut output = format!("### {}\n\n", escape(&table.title));
+    output.push_str(&row(table.header.iter().map(String::as_str)));
+    output.push_str(&row(table.header.iter().map(|_| "---")));
+    for cells in &table.rows {
+        let cells = cells.iter().map(|cell| match &cell.content {
+            Content::Text(text) => text.as_str(),
+            _ => "",
+        });
+        output.push_str(&row(cells));
+    }
+    for note in &table.notes {
+        output.push_str(&format!("\n{}\n", escape(note)));
+    }
+    output
+}
+
+fn row<'a>(cells: impl Iterator<Item = &'a str>) -> String {
+    let cells = cells.map(|cell| escape(cell.trim())).collect::<Vec<_>>();
+    format!("| {} |\n", cells.join(" | "))
+}
+
+/// Escape characters that Markdown would take for formatting
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '|' | '*' | '_' | '`' | '<' | '>' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_tables_and_fences_text() {
+        let json = include_str!("../../../test/msw/forecast.json");
+        let forecast: Vec<crate::msw::forecast::Forecast> = serde_json::from_str(json).unwrap();
+        let markdown = Markdown.render(forecast);
+        // The graph, then a table per day
+        assert!(markdown.starts_with("```\n"));
+        assert_eq!(markdown.matches("```\n").count(), 2);
+        assert_eq!(markdown.matches("### ").count(), 5);
+        assert!(markdown.contains(
+            "\n| Time | Surf | Swell | Wind | Air |\n| --- | --- | --- | --- | --- |\n| 12am | "
+        ));
+    }
+
+    #[test]
+    fn escapes_cells() {
+        assert_eq!(
+            row(["a|b", " *c* "].into_iter()),
+            r"| a\|b | \*c\* |".to_owned() + "\n"
+        );
+    }
+}
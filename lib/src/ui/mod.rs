@@ -1,13 +1,42 @@
+mod ascii;
 mod browser;
+mod markdown;
+mod plain;
+mod png;
 mod render;
+mod svg;
 mod terminal;
+mod theme;
 mod view;
 
+pub use ascii::Ascii;
 pub use browser::Browser;
+pub use markdown::Markdown;
+pub use plain::Plain;
+pub use png::Png;
 pub use render::Render;
-pub use terminal::Terminal;
-pub use view::{rip::Rip, View};
+pub use svg::Svg;
+pub use terminal::{ColorDepth, Terminal};
+pub use theme::Theme;
+pub use view::layout::{Charset, DEFAULT_WIDTH, MAX_WIDTH, MIN_WIDTH};
+pub use view::{
+    compare::Comparison, favorites::Favorites, forecast::Report, rip::Rip, tide::Tides, Color,
+    Layout, Role, Span, Style, View,
+};
 
-pub fn render<R: Render>(view: impl Into<View>) -> R::Output {
-    R::render(view.into())
+pub fn render<R: Render>(renderer: R, view: impl Into<View>) -> R::Output {
+    renderer.render(view.into())
+}
+
+/// Lay out a view for the renderer at the given width, brought within the
+/// supported range
+pub fn layout_width(renderer: &impl Render, view: impl Layout, width: usize) -> View {
+    view.layout(view::layout::clamp(width), renderer.charset())
+}
+
+/// Render a view laid out for the given width, brought within the supported
+/// range
+pub fn render_width<R: Render>(renderer: R, view: impl Layout, width: usize) -> R::Output {
+    let view = layout_width(&renderer, view, width);
+    renderer.render(view)
 }
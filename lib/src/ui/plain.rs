@@ -0,0 +1,24 @@
+//! Rendering logic for plain text, e.g. terminals without color support
+
+use super::render::Render;
+use super::view::{Content, View};
+
+/// Renders the text of a view, ignoring all styles
+pub struct Plain;
+
+impl Render for Plain {
+    type Output = String;
+
+    fn render<V: Into<View>>(&self, view: V) -> Self::Output {
+        let mut output = String::new();
+        for span in view.into().into_text() {
+            match span.content {
+                Content::Text(text) => output.push_str(text.as_str()),
+                Content::Newline => output.push('\n'),
+                // Already replaced by their text
+                Content::Chart(_) | Content::Table(_) => {}
+            }
+        }
+        output
+    }
+}
@@ -0,0 +1,89 @@
+//! Rendering logic for PNG images, e.g. for chat apps that mangle monospace
+//! text on phones
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use resvg::{tiny_skia, usvg};
+
+use super::render::Render;
+use super::svg::Svg;
+use super::theme::Theme;
+use super::view::View;
+
+/// Renders a PNG image by drawing the SVG image with bundled fonts, so it
+/// looks the same wherever it is rendered
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Png {
+    svg: Svg,
+}
+
+impl Png {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.svg = self.svg.theme(theme);
+        self
+    }
+}
+
+/// Pixels per SVG unit, to stay sharp on high density screens
+const SCALE: f32 = 2.0;
+
+const FONT_FAMILY: &str = "DejaVu Sans Mono";
+
+lazy_static! {
+    static ref FONTS: Arc<usvg::fontdb::Database> = {
+        let mut fonts = usvg::fontdb::Database::new();
+        fonts.load_font_data(include_bytes!("../../fonts/DejaVuSansMono.ttf").to_vec());
+        fonts.load_font_data(include_bytes!("../../fonts/DejaVuSansMono-Bold.ttf").to_vec());
+        fonts.set_monospace_family(FONT_FAMILY);
+        Arc::new(fonts)
+    };
+}
+
+impl Render for Png {
+    type Output = Result<Vec<u8>>;
+
+    fn render<V: Into<View>>(&self, view: V) -> Self::Output {
+        let svg = self.svg.render(view);
+        let options = usvg::Options {
+            font_family: FONT_FAMILY.to_owned(),
+            fontdb: FONTS.clone(),
+            ..Default::default()
+        };
+        let tree = usvg::Tree::from_str(&svg, &options)?;
+        let size = tree
+            .size()
+            .to_int_size()
+            .scale_by(SCALE)
+            .ok_or_else(|| anyhow!("Image too large to draw"))?;
+        let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+            .ok_or_else(|| anyhow!("Image too large to draw"))?;
+        resvg::render(
+            &tree,
+            tiny_skia::Transform::from_scale(SCALE, SCALE),
+            &mut pixmap.as_mut(),
+        );
+        Ok(pixmap.encode_png()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draws_forecast() {
+        let json = include_str!("../../../test/msw/forecast.json");
+        let forecast: Vec<crate::msw::forecast::Forecast> = serde_json::from_str(json).unwrap();
+        let png = Png::new().render(forecast).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        let image = tiny_skia::Pixmap::decode_png(&png).unwrap();
+        // The default width of 90 columns, at twice the size
+        assert_eq!(image.width(), (2.0 * (90.0 * 8.4 + 2.0 * 12.0)) as u32);
+    }
+}
@@ -1,4 +1,4 @@
-use super::view::View;
+use super::view::{layout::Charset, View};
 
 /// Specific way in which a given UI will render a `View`.
 pub trait Render {
@@ -6,5 +6,10 @@ pub trait Render {
     // specific type
     type Output;
 
-    fn render<V: Into<View>>(view: V) -> Self::Output;
+    fn render<V: Into<View>>(&self, view: V) -> Self::Output;
+
+    /// Characters the output can show, for views to be laid out with
+    fn charset(&self) -> Charset {
+        Charset::Unicode
+    }
 }
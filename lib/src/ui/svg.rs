@@ -0,0 +1,344 @@
+//! Rendering logic for SVG images, e.g. to embed in wikis and chat
+
+use super::render::Render;
+use super::theme::Theme;
+use super::view::chart::Chart;
+use super::view::{Content, Role, Span, View};
+
+/// Renders an SVG image, with text laid out on a monospace grid and charts
+/// drawn as vectors
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Svg {
+    theme: Theme,
+}
+
+impl Svg {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+const FONT_SIZE: f32 = 14.0;
+/// Size of a character cell, in pixels, for the font size
+const CHAR_WIDTH: f32 = 8.4;
+const LINE_HEIGHT: f32 = 16.5;
+/// Offset of the text baseline from the top of its line
+const BASELINE: f32 = 12.5;
+/// Space around the content
+const PADDING: f32 = 12.0;
+/// Characters left of a chart's plot, for the height axis labels
+const AXIS_WIDTH: usize = 8;
+
+impl Render for Svg {
+    type Output = String;
+
+    fn render<V: Into<View>>(&self, view: V) -> Self::Output {
+        let rows = rows(view.into());
+        let columns = rows.iter().map(Row::columns).max().unwrap_or(0);
+        let lines = rows.iter().map(Row::lines).sum::<usize>();
+        let width = columns as f32 * CHAR_WIDTH + 2.0 * PADDING;
+        let height = lines as f32 * LINE_HEIGHT + 2.0 * PADDING;
+        let (background, foreground) = self.theme.page();
+        let mut output = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.1}" height="{height:.1}" viewBox="0 0 {width:.1} {height:.1}" font-family="'Fira Code', 'DejaVu Sans Mono', monospace" font-size="{FONT_SIZE}" color="{foreground}" fill="{foreground}" xml:space="preserve">
+<rect width="100%" height="100%" fill="{background}"/>
+"#
+        );
+        let mut line = 0;
+        for row in &rows {
+            let top = PADDING + line as f32 * LINE_HEIGHT;
+            match row {
+                Row::Text(spans) => self.text(&mut output, spans, top),
+                Row::Chart(chart) => self.chart(&mut output, chart, top),
+            }
+            line += row.lines();
+        }
+        output.push_str("</svg>\n");
+        output
+    }
+}
+
+impl Svg {
+    /// A line of text, with each span placed on the character grid
+    fn text(&self, output: &mut String, spans: &[Span], top: f32) {
+        let mut column = 0;
+        let mut tspans = String::new();
+        for span in spans {
+            let text = match &span.content {
+                Content::Text(text) => text,
+                _ => continue,
+            };
+            let chars = text.chars().count();
+            let style = self.theme.apply(span.style);
+            let x = PADDING + column as f32 * CHAR_WIDTH;
+            if let Some(color) = style.bg {
+                output.push_str(&format!(
+                    r#"<rect x="{x:.1}" y="{top:.1}" width="{:.1}" height="{LINE_HEIGHT}" fill="{}"/>"#,
+                    chars as f32 * CHAR_WIDTH,
+                    self.theme.css(color)
+                ));
+                output.push('\n');
+            }
+            if !text.trim().is_empty() {
+                let mut attributes = format!(r#" x="{x:.1}""#);
+                if let Some(color) = style.fg {
+                    attributes.push_str(&format!(r#" fill="{}""#, self.theme.css(color)));
+                }
+                if style.bold {
+                    attributes.push_str(r#" font-weight="bold""#);
+                }
+                tspans.push_str(&format!("<tspan{attributes}>{}</tspan>", escape(text)));
+            }
+            column += chars;
+        }
+        if !tspans.is_empty() {
+            output.push_str(&format!(
+                r#"<text y="{:.1}">{tspans}</text>"#,
+                top + BASELINE
+            ));
+            output.push('\n');
+        }
+    }
+
+    /// A chart in the space its text would take up: the swell height as an
+    /// area colored by rating, over day labels and arrows pointing the way
+    /// the wind blows
+    fn chart(&self, output: &mut String, chart: &Chart, top: f32) {
+        let width = columns(&chart.text) as f32 * CHAR_WIDTH;
+        let height = chart.lines() as f32 * LINE_HEIGHT;
+        let (background, _) = self.theme.page();
+        let axis = self.paint(Role::Axis);
+
+        // Frame, with the title over its top edge
+        output.push_str(&format!(
+            r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" rx="4" fill="none" stroke="{}"/>"#,
+            PADDING + CHAR_WIDTH / 2.0,
+            top + LINE_HEIGHT / 2.0,
+            width - CHAR_WIDTH,
+            height - LINE_HEIGHT,
+            self.paint(Role::Border)
+        ));
+        output.push('\n');
+        let center = PADDING + width / 2.0;
+        let title_width = (chart.title.chars().count() + 2) as f32 * CHAR_WIDTH;
+        output.push_str(&format!(
+            r#"<rect x="{:.1}" y="{top:.1}" width="{title_width:.1}" height="{LINE_HEIGHT}" fill="{background}"/>"#,
+            center - title_width / 2.0
+        ));
+        output.push_str(&format!(
+            r#"<text x="{center:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+            top + BASELINE,
+            escape(&chart.title)
+        ));
+        output.push('\n');
+
+        let left = PADDING + AXIS_WIDTH as f32 * CHAR_WIDTH;
+        let right = PADDING + width - 2.0 * CHAR_WIDTH;
+        let plot_top = top + 2.0 * LINE_HEIGHT;
+        let plot_bottom = top + height - 3.0 * LINE_HEIGHT;
+
+        // Height axis
+        for (y, label) in [
+            (plot_top, format!("{} {}", chart.max_height, chart.unit)),
+            (plot_bottom, format!("0 {}", chart.unit)),
+        ] {
+            output.push_str(&format!(
+                r#"<line x1="{left:.1}" y1="{y:.1}" x2="{right:.1}" y2="{y:.1}" stroke="{axis}" stroke-opacity="0.3"/>"#
+            ));
+            output.push_str(&format!(
+                r#"<text x="{:.1}" y="{:.1}" text-anchor="end" fill="{axis}">{label}</text>"#,
+                left - CHAR_WIDTH / 2.0,
+                y + FONT_SIZE / 3.0
+            ));
+            output.push('\n');
+        }
+
+        let points = &chart.points;
+        if points.is_empty() {
+            return;
+        }
+        let step = (right - left) / points.len() as f32;
+        let x = |ix: usize| left + (ix as f32 + 0.5) * step;
+        let y = |height: f32| {
+            let proportion = (height / chart.max_height).clamp(0.0, 1.0);
+            plot_bottom - proportion * (plot_bottom - plot_top)
+        };
+
+        // Swell height, each stretch colored by the rating at its start
+        for (ix, pair) in points.windows(2).enumerate() {
+            let (x1, y1) = (x(ix), y(pair[0].height));
+            let (x2, y2) = (x(ix + 1), y(pair[1].height));
+            let paint = self.paint(pair[0].role);
+            output.push_str(&format!(
+                r#"<polygon points="{x1:.1},{plot_bottom:.1} {x1:.1},{y1:.1} {x2:.1},{y2:.1} {x2:.1},{plot_bottom:.1}" fill="{paint}" fill-opacity="0.35"/>"#
+            ));
+            output.push_str(&format!(
+                r#"<line x1="{x1:.1}" y1="{y1:.1}" x2="{x2:.1}" y2="{y2:.1}" stroke="{paint}" stroke-width="2"/>"#
+            ));
+            output.push('\n');
+        }
+
+        // Days, divided at midnight
+        for (ix, point) in points.iter().enumerate() {
+            if ix > 0 && points[ix - 1].time.date() == point.time.date() {
+                continue;
+            }
+            let start = x(ix) - step / 2.0;
+            if ix > 0 {
+                output.push_str(&format!(
+                    r#"<line x1="{start:.1}" y1="{plot_top:.1}" x2="{start:.1}" y2="{plot_bottom:.1}" stroke="{axis}" stroke-opacity="0.5" stroke-dasharray="2 3"/>"#
+                ));
+            }
+            output.push_str(&format!(
+                r#"<text x="{:.1}" y="{:.1}" fill="{axis}">{}</text>"#,
+                start + CHAR_WIDTH / 2.0,
+                plot_bottom + BASELINE,
+                point.time.format("%a")
+            ));
+            output.push('\n');
+        }
+
+        // Wind, skipping times evenly when the arrows would crowd each other
+        let stride = ((1.5 * CHAR_WIDTH / step).ceil() as usize).max(1);
+        let arrows = plot_bottom + 1.5 * LINE_HEIGHT;
+        for (ix, point) in points.iter().enumerate().step_by(stride) {
+            let wind = &point.wind;
+            output.push_str(&format!(
+                r#"<path d="M0,5V-5M-3,-2L0,-5L3,-2" transform="translate({:.1},{arrows:.1}) rotate({:.0})" fill="none" stroke="currentColor"><title>{:?} {}{}</title></path>"#,
+                x(ix),
+                wind.direction,
+                wind.compass_direction,
+                wind.speed,
+                wind.unit
+            ));
+            output.push('\n');
+        }
+    }
+
+    /// Color of spans with the role, defaulting to the text color
+    fn paint(&self, role: Role) -> String {
+        match self.theme.style(role).fg {
+            Some(color) => self.theme.css(color),
+            None => "currentColor".to_owned(),
+        }
+    }
+}
+
+/// A line of text, or a chart standing in for several
+enum Row {
+    Text(Vec<Span>),
+    Chart(Box<Chart>),
+}
+
+impl Row {
+    fn lines(&self) -> usize {
+        match self {
+            Row::Text(_) => 1,
+            Row::Chart(chart) => chart.lines(),
+        }
+    }
+
+    fn columns(&self) -> usize {
+        match self {
+            Row::Text(spans) => columns(spans),
+            Row::Chart(chart) => columns(&chart.text),
+        }
+    }
+}
+
+/// Split the view into rows, one per line of text or chart
+fn rows(view: View) -> Vec<Row> {
+    let mut rows = vec![];
+    let mut line = vec![];
+    // The newline after a chart ends its last line, rather than an empty one
+    let mut after_chart = false;
+    for span in view.spans {
+        match span.content {
+            Content::Text(_) => line.push(span),
+            Content::Newline if after_chart => {}
+            Content::Newline => rows.push(Row::Text(std::mem::take(&mut line))),
+            Content::Chart(chart) => {
+                if !line.is_empty() {
+                    rows.push(Row::Text(std::mem::take(&mut line)));
+                }
+                rows.push(Row::Chart(chart));
+                after_chart = true;
+                continue;
+            }
+            // Drawn as its text, line by line
+            Content::Table(table) => {
+                for span in table.text {
+                    match span.content {
+                        Content::Newline => rows.push(Row::Text(std::mem::take(&mut line))),
+                        _ => line.push(span),
+                    }
+                }
+            }
+        }
+        after_chart = false;
+    }
+    if !line.is_empty() {
+        rows.push(Row::Text(line));
+    }
+    rows
+}
+
+/// Width of the widest line
+fn columns(spans: &[Span]) -> usize {
+    spans
+        .split(|span| span.content == Content::Newline)
+        .map(|line| {
+            line.iter()
+                .map(|span| match &span.content {
+                    Content::Text(text) => text.chars().count(),
+                    _ => 0,
+                })
+                .sum()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::view::Color;
+
+    #[test]
+    fn places_text_on_grid() {
+        let mut warning = Span::new("<oops>");
+        warning.style().fg(Color::Red).bold();
+        let svg = Svg::new().render(View {
+            spans: vec![Span::new("hi"), Span::newline(), Span::new("  "), warning],
+        });
+        assert!(svg.contains(r#"<text y="24.5"><tspan x="12.0">hi</tspan></text>"#));
+        assert!(svg.contains(
+            r##"<tspan x="28.8" fill="#cc241d" font-weight="bold">&lt;oops&gt;</tspan>"##
+        ));
+        assert!(svg.contains(r#"height="57.0""#));
+    }
+
+    #[test]
+    fn draws_charts_in_place_of_text() {
+        let json = include_str!("../../../test/msw/forecast.json");
+        let forecast: Vec<crate::msw::forecast::Forecast> = serde_json::from_str(json).unwrap();
+        let points = forecast.len();
+        let text = crate::ui::render(crate::ui::Plain, forecast.clone());
+        let svg = Svg::new().render(forecast);
+        let height = text.lines().count() as f32 * LINE_HEIGHT + 2.0 * PADDING;
+        assert!(svg.contains(&format!(r#"height="{height:.1}""#)));
+        assert_eq!(svg.matches("<polygon").count(), points - 1);
+        assert!(svg.contains("rotate("));
+    }
+}
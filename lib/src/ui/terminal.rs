@@ -1,24 +1,109 @@
 //! Rendering logic for terminals
 
+use std::env;
+
+use serde::Deserialize;
+
 use super::render::Render;
+use super::theme::Theme;
 use super::view::{Color, Content, View};
 
-pub struct Terminal;
+/// Renders ANSI escape codes, limited to the colors the terminal can show
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Terminal {
+    depth: ColorDepth,
+    theme: Theme,
+}
+
+impl Terminal {
+    pub fn new(depth: ColorDepth) -> Self {
+        Self {
+            depth,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+/// How many colors a terminal can show. Colors beyond its depth are replaced
+/// by the closest it has.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+pub enum ColorDepth {
+    /// The 16 standard colors, which every color terminal supports
+    #[default]
+    #[serde(rename = "16")]
+    Basic,
+    /// The 256 color palette of xterm and friends
+    #[serde(rename = "256")]
+    Ansi256,
+    /// 24-bit color
+    #[serde(rename = "truecolor")]
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Guess the depth from the conventional `COLORTERM` and `TERM` variables
+    pub fn from_env() -> Self {
+        let var = |name| env::var(name).unwrap_or_default();
+        let colorterm = var("COLORTERM");
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            ColorDepth::TrueColor
+        } else if var("TERM").contains("256color") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Basic
+        }
+    }
+
+    /// The closest color this depth can show
+    fn downgrade(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorDepth::TrueColor, _) => color,
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Fixed(to_fixed(r, g, b)),
+            (ColorDepth::Ansi256, _) => color,
+            (ColorDepth::Basic, Color::Fixed(ix @ 0..=15)) => match ix {
+                0..=7 => Color::NAMED[ix as usize],
+                8 => Color::Gray,
+                // Bright variants of the standard colors
+                _ => Color::NAMED[ix as usize - 8],
+            },
+            (ColorDepth::Basic, Color::Fixed(_) | Color::Rgb(..)) => nearest(color.rgb()),
+            (ColorDepth::Basic, _) => color,
+        }
+    }
+}
 
 impl Render for Terminal {
     type Output = String;
 
-    fn render<V: Into<View>>(view: V) -> Self::Output {
+    fn render<V: Into<View>>(&self, view: V) -> Self::Output {
         let mut output = String::new();
-        for span in view.into().spans {
-            if let Some(color) = span.style.fg {
-                output.push_str(to_code(color));
+        for span in view.into().into_text() {
+            let style = self.theme.apply(span.style);
+            let mut codes = vec![];
+            if style.bold {
+                codes.push("1".to_owned());
+            }
+            if let Some(color) = style.fg {
+                codes.push(to_code(self.depth.downgrade(color), Layer::Foreground));
+            }
+            if let Some(color) = style.bg {
+                codes.push(to_code(self.depth.downgrade(color), Layer::Background));
+            }
+            if !codes.is_empty() {
+                output.push_str(&format!("\x1B[0;{}m", codes.join(";")));
             }
             match span.content {
                 Content::Text(text) => output.push_str(text.as_str()),
                 Content::Newline => output.push('\n'),
+                // Already replaced by their text
+                Content::Chart(_) | Content::Table(_) => {}
             }
-            if span.style.fg.is_some() {
+            if !codes.is_empty() {
                 output.push_str(ansi::RESET);
             }
         }
@@ -26,17 +111,124 @@ impl Render for Terminal {
     }
 }
 
-fn to_code(color: Color) -> &'static str {
+enum Layer {
+    Foreground,
+    Background,
+}
+
+/// SGR parameters to set the color
+fn to_code(color: Color, layer: Layer) -> String {
+    let (offset, extended) = match layer {
+        Layer::Foreground => (0, 38),
+        Layer::Background => (10, 48),
+    };
+    let named = |code: u8| (code + offset).to_string();
     match color {
-        Color::Red => ansi::RED,
-        Color::Blue => ansi::BLUE,
-        Color::Green => ansi::GREEN,
+        Color::Black => named(ansi::BLACK),
+        Color::Red => named(ansi::RED),
+        Color::Green => named(ansi::GREEN),
+        Color::Yellow => named(ansi::YELLOW),
+        Color::Blue => named(ansi::BLUE),
+        Color::Magenta => named(ansi::MAGENTA),
+        Color::Cyan => named(ansi::CYAN),
+        Color::White => named(ansi::WHITE),
+        Color::Gray => named(ansi::GRAY),
+        Color::Fixed(ix) => format!("{extended};5;{ix}"),
+        Color::Rgb(r, g, b) => format!("{extended};2;{r};{g};{b}"),
+    }
+}
+
+/// The closest color in the 6x6x6 cube or the grayscale ramp of the 256 color
+/// palette
+fn to_fixed(r: u8, g: u8, b: u8) -> u8 {
+    let level = |c: u8| match c {
+        0..=47 => 0,
+        48..=114 => 1,
+        _ => (c - 35) / 40,
+    };
+    let cube = 16 + 36 * level(r) + 6 * level(g) + level(b);
+    let avg = (r as u16 + g as u16 + b as u16) / 3;
+    let gray = 232 + ((avg.saturating_sub(3) / 10) as u8).min(23);
+    if distance(Color::Fixed(gray).rgb(), (r, g, b)) < distance(Color::Fixed(cube).rgb(), (r, g, b))
+    {
+        gray
+    } else {
+        cube
     }
 }
 
+/// The closest of the standard colors
+fn nearest(rgb: (u8, u8, u8)) -> Color {
+    Color::NAMED
+        .into_iter()
+        .min_by_key(|color| distance(color.rgb(), rgb))
+        .unwrap()
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
 mod ansi {
-    pub const RED: &str = "\x1B[0;31m";
-    pub const GREEN: &str = "\x1B[0;32m";
-    pub const BLUE: &str = "\x1B[0;34m";
+    pub const BLACK: u8 = 30;
+    pub const RED: u8 = 31;
+    pub const GREEN: u8 = 32;
+    pub const YELLOW: u8 = 33;
+    pub const BLUE: u8 = 34;
+    pub const MAGENTA: u8 = 35;
+    pub const CYAN: u8 = 36;
+    pub const WHITE: u8 = 37;
+    pub const GRAY: u8 = 90;
     pub const RESET: &str = "\x1B[0m";
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rating::Level;
+    use crate::ui::view::{Role, Span};
+
+    fn render(depth: ColorDepth, span: Span) -> String {
+        Terminal::new(depth).render(View { spans: vec![span] })
+    }
+
+    #[test]
+    fn renders_styles() {
+        let mut span = Span::new("hi");
+        span.style().fg(Color::Red);
+        assert_eq!(
+            render(ColorDepth::Basic, span.clone()),
+            "\x1B[0;31mhi\x1B[0m"
+        );
+        span.style().bg(Color::Blue).bold();
+        assert_eq!(render(ColorDepth::Basic, span), "\x1B[0;1;31;44mhi\x1B[0m");
+        assert_eq!(render(ColorDepth::Basic, Span::new("hi")), "hi");
+    }
+
+    #[test]
+    fn downgrades_colors() {
+        let mut span = Span::new("hi");
+        span.style()
+            .fg(Color::Rgb(250, 10, 10))
+            .bg(Color::Fixed(110));
+        assert_eq!(
+            render(ColorDepth::TrueColor, span.clone()),
+            "\x1B[0;38;2;250;10;10;48;5;110mhi\x1B[0m"
+        );
+        assert_eq!(
+            render(ColorDepth::Ansi256, span.clone()),
+            "\x1B[0;38;5;196;48;5;110mhi\x1B[0m"
+        );
+        assert_eq!(render(ColorDepth::Basic, span), "\x1B[0;31;100mhi\x1B[0m");
+        assert_eq!(to_fixed(128, 128, 128), 244);
+        let mut span = Span::new("hi");
+        span.style().role(Role::Rating(Level::Good));
+        let terminal = Terminal::new(ColorDepth::Basic).theme(Theme::Colorblind);
+        assert_eq!(
+            terminal.render(View { spans: vec![span] }),
+            "\x1B[0;36mhi\x1B[0m"
+        );
+        assert_eq!(ColorDepth::Basic.downgrade(Color::Fixed(12)), Color::Blue);
+    }
+}
@@ -0,0 +1,172 @@
+//! Named themes, deciding how each [`Role`] looks in terminals and browsers
+
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, Error};
+use serde::Deserialize;
+
+use super::view::{Color, Role, Style};
+use crate::rating::Level;
+
+/// A look for the forecast. Terminals show the named colors from their own
+/// palette, so the browser additionally gets a palette and page colors.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+    /// Blue, orange, and vermillion, which stay distinct with the common
+    /// forms of color blindness
+    Colorblind,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 4] = [
+        Theme::Dark,
+        Theme::Light,
+        Theme::HighContrast,
+        Theme::Colorblind,
+    ];
+
+    /// How spans with the role look
+    pub fn style(self, role: Role) -> Style {
+        let mut style = Style::default();
+        match (self, role) {
+            (Theme::Dark, Role::Rating(Level::Good)) => style.fg(Color::Green),
+            (Theme::Dark, Role::Rating(Level::Fair)) => style.fg(Color::Blue),
+            (Theme::Dark, Role::Rating(Level::Poor) | Role::Warning) => style.fg(Color::Red),
+            (Theme::Dark, Role::Night | Role::Muted) => style.fg(Color::Gray),
+            // Darker shades, to stand out against a light background
+            (Theme::Light, Role::Rating(Level::Good)) => style.fg(Color::Fixed(28)),
+            (Theme::Light, Role::Rating(Level::Fair)) => style.fg(Color::Fixed(25)),
+            (Theme::Light, Role::Rating(Level::Poor) | Role::Warning) => {
+                style.fg(Color::Fixed(124))
+            }
+            (Theme::Light, Role::Night | Role::Muted) => style.fg(Color::Fixed(246)),
+            (Theme::HighContrast, Role::Rating(Level::Good)) => style.fg(Color::Green).bold(),
+            (Theme::HighContrast, Role::Rating(Level::Fair)) => style.fg(Color::Yellow).bold(),
+            (Theme::HighContrast, Role::Rating(Level::Poor) | Role::Warning) => {
+                style.fg(Color::Red).bold()
+            }
+            // Night and muted text stay legible, rather than fading out
+            (Theme::HighContrast, Role::Axis | Role::Legend | Role::Border) => style.bold(),
+            (Theme::Colorblind, Role::Rating(Level::Good)) => style.fg(Color::Rgb(0, 114, 178)),
+            (Theme::Colorblind, Role::Rating(Level::Fair)) => style.fg(Color::Rgb(230, 159, 0)),
+            (Theme::Colorblind, Role::Rating(Level::Poor) | Role::Warning) => {
+                style.fg(Color::Rgb(213, 94, 0))
+            }
+            (Theme::Colorblind, Role::Night | Role::Muted) => style.fg(Color::Gray),
+            _ => &mut style,
+        };
+        style
+    }
+
+    /// The style with its role's look filled in, keeping any explicit colors
+    pub fn apply(self, style: Style) -> Style {
+        let themed = match style.role {
+            Some(role) => self.style(role),
+            None => return style,
+        };
+        Style {
+            fg: style.fg.or(themed.fg),
+            bg: style.bg.or(themed.bg),
+            bold: style.bold || themed.bold,
+            role: style.role,
+        }
+    }
+
+    /// Background and text colors of the page in a browser or image
+    pub(super) fn page(self) -> (&'static str, &'static str) {
+        match self {
+            Theme::Dark | Theme::Colorblind => ("#282828", "#ebdbb2"),
+            Theme::Light => ("#fbf1c7", "#3c3836"),
+            Theme::HighContrast => ("#000000", "#ffffff"),
+        }
+    }
+
+    /// CSS value of a color, from the palette for the named colors
+    pub(super) fn css(self, color: Color) -> String {
+        match Color::NAMED.iter().position(|named| *named == color) {
+            Some(ix) => self.palette()[ix].to_owned(),
+            None => {
+                let (r, g, b) = color.rgb();
+                format!("#{r:02x}{g:02x}{b:02x}")
+            }
+        }
+    }
+
+    /// CSS values for the named colors in a browser, in palette order
+    fn palette(self) -> [&'static str; 9] {
+        match self {
+            Theme::Dark | Theme::Colorblind => [
+                "#1d2021", "#cc241d", "#98971a", "#d79921", "#bbbbbb", "#b16286", "#689d6a",
+                "#ebdbb2", "#665c54",
+            ],
+            Theme::Light => [
+                "#282828", "#9d0006", "#79740e", "#b57614", "#076678", "#8f3f71", "#427b58",
+                "#f9f5d7", "#928374",
+            ],
+            Theme::HighContrast => [
+                "#000000", "#ff5555", "#55ff55", "#ffff55", "#5599ff", "#ff55ff", "#55ffff",
+                "#ffffff", "#bbbbbb",
+            ],
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Theme::Dark => "dark",
+            Theme::Light => "light",
+            Theme::HighContrast => "high-contrast",
+            Theme::Colorblind => "colorblind",
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for Theme {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Theme::ALL
+            .into_iter()
+            .find(|theme| theme.name() == s)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Unknown theme {s}, expected one of: {}",
+                    Theme::ALL.map(Theme::name).join(", ")
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_names() {
+        for theme in Theme::ALL {
+            assert_eq!(theme.to_string().parse::<Theme>().unwrap(), theme);
+        }
+        assert!("solarized".parse::<Theme>().is_err());
+    }
+
+    #[test]
+    fn explicit_colors_win() {
+        let mut style = Style::default();
+        style.role(Role::Rating(Level::Good));
+        assert_eq!(Theme::Dark.apply(style).fg, Some(Color::Green));
+        assert!(Theme::HighContrast.apply(style).bold);
+        style.fg(Color::Magenta);
+        assert_eq!(Theme::Dark.apply(style).fg, Some(Color::Magenta));
+        assert_eq!(Theme::Dark.apply(Style::default()), Style::default());
+    }
+}
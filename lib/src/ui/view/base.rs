@@ -1,3 +1,7 @@
+use super::chart::Chart;
+use super::table::Table;
+use crate::rating::Level;
+
 pub const LINE_VERT: &str = "│";
 pub const LINE_HORIZONTAL: &str = "─";
 pub const CORNER_TOP_LEFT: &str = "┌";
@@ -13,12 +17,27 @@ pub struct View {
     pub spans: Vec<Span>,
 }
 
+impl View {
+    /// The spans with each chart and table replaced by its text, for renderers
+    /// that only draw text
+    pub fn into_text(self) -> Vec<Span> {
+        self.spans
+            .into_iter()
+            .flat_map(|span| match span.content {
+                Content::Chart(chart) => chart.text,
+                Content::Table(table) => table.text,
+                _ => vec![span],
+            })
+            .collect()
+    }
+}
+
 /// Internal type synonym to distinguish line breaks on inner widgets
 pub type Line = Vec<Span>;
 
 /// A contiguous piece of content with consistent styles. These shouldn't need to
-/// nest.
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// nest, apart from the text of a chart or table.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Span {
     pub content: Content,
     pub style: Style,
@@ -41,6 +60,22 @@ impl Span {
         }
     }
 
+    /// Create a chart, drawn as its text by renderers that only draw text
+    pub fn chart(chart: Chart) -> Self {
+        Self {
+            content: Content::Chart(Box::new(chart)),
+            style: Style::default(),
+        }
+    }
+
+    /// Create a table, drawn as its text by renderers that only draw text
+    pub fn table(table: Table) -> Self {
+        Self {
+            content: Content::Table(Box::new(table)),
+            style: Style::default(),
+        }
+    }
+
     pub fn style(&mut self) -> &mut Style {
         &mut self.style
     }
@@ -57,10 +92,12 @@ pub(super) use span;
 /// Content is typically just text in the form of a String. But I think it will
 /// make life easier to separate control chars like newlines. So, try not to
 /// sneak those into the text values.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Content {
     Text(String),
     Newline,
+    Chart(Box<Chart>),
+    Table(Box<Table>),
 }
 
 /// Style attributes that can be added to a given span.
@@ -69,6 +106,9 @@ pub struct Style {
     pub fg: Option<Color>,
     pub bg: Option<Color>,
     pub bold: bool,
+    /// What the span means, for the theme to style. Explicit colors take
+    /// precedence.
+    pub role: Option<Role>,
 }
 
 impl Style {
@@ -86,15 +126,115 @@ impl Style {
         self.bold = true;
         self
     }
+
+    pub fn role(&mut self, role: Role) -> &mut Self {
+        self.role = Some(role);
+        self
+    }
+}
+
+/// What a span means, independent of how it looks. Themes decide the look,
+/// and the browser also marks it up, e.g. `data-rating="good"`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// How worthwhile the conditions are
+    Rating(Level),
+    /// Hours of darkness
+    Night,
+    /// Labels on the axes of graphs
+    Axis,
+    /// Labels of table rows and columns
+    Legend,
+    /// Lines boxing in a view
+    Border,
+    /// Something went wrong, e.g. a favorite failed to load
+    Warning,
+    /// Of little interest, e.g. a value unchanged between forecast runs
+    Muted,
 }
 
-/// The colors available for styling.
-// Add more as necessary
+/// A row or column label, centered within the width
+pub(super) fn label(text: &str, width: usize) -> Span {
+    let mut span = span!("{:^width$}", text);
+    span.style().role(Role::Legend);
+    span
+}
+
+/// The colors available for styling. The named colors are the standard
+/// terminal palette, which every renderer supports; renderers approximate the
+/// others as best they can.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Color {
+    Black,
+    Red,
     Green,
+    Yellow,
     Blue,
-    Red,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+    /// An index into the 256 color palette of xterm and friends
+    Fixed(u8),
+    /// A 24-bit color
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// The standard colors, in palette order
+    pub const NAMED: [Color; 9] = [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::White,
+        Color::Gray,
+    ];
+
+    /// Red, green, and blue components, per xterm's default palette for the
+    /// named and fixed colors
+    pub fn rgb(self) -> (u8, u8, u8) {
+        match self {
+            Color::Black => (0, 0, 0),
+            Color::Red => (205, 0, 0),
+            Color::Green => (0, 205, 0),
+            Color::Yellow => (205, 205, 0),
+            Color::Blue => (0, 0, 238),
+            Color::Magenta => (205, 0, 205),
+            Color::Cyan => (0, 205, 205),
+            Color::White => (229, 229, 229),
+            Color::Gray => (127, 127, 127),
+            Color::Fixed(ix @ 0..=15) => {
+                let bright = [
+                    (255, 0, 0),
+                    (0, 255, 0),
+                    (255, 255, 0),
+                    (92, 92, 255),
+                    (255, 0, 255),
+                    (0, 255, 255),
+                    (255, 255, 255),
+                ];
+                match ix {
+                    0..=7 => Color::NAMED[ix as usize].rgb(),
+                    8 => Color::Gray.rgb(),
+                    _ => bright[ix as usize - 9],
+                }
+            }
+            Color::Fixed(ix @ 16..=231) => {
+                let level = |n: u8| if n == 0 { 0 } else { 55 + 40 * n };
+                let ix = ix - 16;
+                (level(ix / 36), level(ix / 6 % 6), level(ix % 6))
+            }
+            Color::Fixed(ix) => {
+                let gray = 8 + 10 * (ix - 232);
+                (gray, gray, gray)
+            }
+            Color::Rgb(r, g, b) => (r, g, b),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -118,4 +258,14 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn fixed_colors_to_rgb() {
+        assert_eq!(Color::Fixed(1).rgb(), Color::Red.rgb());
+        assert_eq!(Color::Fixed(16).rgb(), (0, 0, 0));
+        assert_eq!(Color::Fixed(196).rgb(), (255, 0, 0));
+        assert_eq!(Color::Fixed(110).rgb(), (135, 175, 215));
+        assert_eq!(Color::Fixed(232).rgb(), (8, 8, 8));
+        assert_eq!(Color::Fixed(255).rgb(), (238, 238, 238));
+    }
 }
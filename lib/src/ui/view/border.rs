@@ -0,0 +1,107 @@
+//! Titled borders shared by the boxed views
+
+use super::base::*;
+
+// TODO: make a more elegant abstraction than this?
+pub(super) trait Border {
+    /// Title string (unpadded)
+    fn title(&self) -> String;
+
+    /// Total width of the viewpoint output, including the border
+    fn width(&self) -> usize;
+
+    /// Viewpoint width minus the border chars
+    fn interior_width(&self) -> usize {
+        self.width() - 2
+    }
+
+    /// Contents within the border
+    fn draw_inner(&self) -> Vec<Line>;
+
+    /// Default `draw()` is to border the contents within the border.
+    fn draw(self) -> Vec<Span>
+    where
+        Self: Sized,
+    {
+        self.border(self.draw_inner())
+    }
+
+    /// Wrap inner view with a titled border.
+    fn border(&self, inner: Vec<Line>) -> Vec<Span> {
+        // Top border of the view manually handles border offsets
+        let mut spans = self.border_top();
+        spans.push(Span::newline());
+
+        // Wrap border around each interior line
+        let border_wrap = [edge(LINE_VERT), Span::newline(), edge(LINE_VERT)];
+        spans.push(edge(LINE_VERT));
+        spans.extend(inner.as_slice().join(&border_wrap[..]));
+        spans.push(edge(LINE_VERT));
+        spans.push(Span::newline());
+
+        // Bottom border of the view manually handles border offsets
+        spans.extend(self.border_bottom());
+
+        spans
+    }
+
+    /// Render border top title
+    fn border_top(&self) -> Vec<Span> {
+        // Leave room for the title's own box, eliding any overflow
+        let mut title = self.title();
+        let max_width = self.interior_width() - 4;
+        if title.chars().count() > max_width {
+            title = title.chars().take(max_width - 1).collect::<String>() + "…";
+        }
+        let title_width = title.chars().count() + 2;
+        let title = format!(" {title} ");
+        // top line
+        let box_top = format!(
+            "{CORNER_TOP_LEFT}{:─^width$}{CORNER_TOP_RIGHT}",
+            "",
+            width = title_width
+        );
+        let top = format!("{:^width$}", box_top, width = self.width());
+        // middle line
+        let box_mid = format!("{TEE_LEFT}{title}{TEE_RIGHT}");
+        let mid = format!(
+            "{CORNER_TOP_LEFT}{:─^width$}{CORNER_TOP_RIGHT}",
+            box_mid,
+            width = self.interior_width()
+        );
+        // bottom line
+        let box_btm = format!(
+            "{CORNER_BTM_LEFT}{:─^width$}{CORNER_BTM_RIGHT}",
+            "",
+            width = title_width
+        );
+        let btm = format!(
+            "{LINE_VERT}{:^width$}{LINE_VERT}",
+            box_btm,
+            width = self.interior_width()
+        );
+        vec![
+            edge(top),
+            Span::newline(),
+            edge(mid),
+            Span::newline(),
+            edge(btm),
+        ]
+    }
+
+    /// Closing for the bottom of the border box
+    fn border_bottom(&self) -> Vec<Span> {
+        vec![edge(format!(
+            "{CORNER_BTM_LEFT}{:─^width$}{CORNER_BTM_RIGHT}",
+            "",
+            width = self.interior_width()
+        ))]
+    }
+}
+
+/// A piece of the border
+fn edge(text: impl Into<String>) -> Span {
+    let mut span = Span::new(text);
+    span.style().role(Role::Border);
+    span
+}
@@ -0,0 +1,208 @@
+use std::fmt::Display;
+
+use super::base::*;
+use super::border::Border;
+use super::forecast::{bearing, compass};
+use super::layout::{max_columns, Charset, Layout};
+use crate::msw::forecast::CompassDirection;
+use crate::ndbc::{Buoy, BuoyObservation, WaveComponent};
+
+impl Layout for Buoy {
+    /// Transform buoy observations into stylized text snippets
+    fn layout(self, width: usize, charset: Charset) -> View {
+        let buoy = self;
+        let spans = if buoy.observations.is_empty() {
+            vec![
+                span!("No recent observations for buoy {}", buoy.station),
+                Span::newline(),
+            ]
+        } else {
+            Observations::new(&buoy, width, charset).draw()
+        };
+        View { spans }
+    }
+}
+
+/// The most recent observations from a buoy, one per column
+struct Observations<'a> {
+    station: &'a str,
+    observations: Vec<&'a BuoyObservation>,
+    width: usize,
+    bin_width: usize,
+    right_margin: usize,
+    charset: Charset,
+}
+
+impl Border for Observations<'_> {
+    fn title(&self) -> String {
+        let latest = self.observations.last().unwrap().timestamp;
+        format!(
+            "Buoy {} - {} UTC",
+            self.station,
+            latest.format("%a %b %d %H:%M")
+        )
+    }
+
+    // Rows:
+    //   Time
+    //   Waves: Height, Period, Direction
+    //   Swell: Height, Period, Direction
+    //   Wind: Speed (gusts), Direction
+    //   Water temp
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn draw_inner(&self) -> Vec<Line> {
+        let skip_line = vec![span!("{:^width$}", "", width = self.interior_width())];
+
+        let mut lines = vec![];
+        lines.extend(self.time());
+        lines.push(skip_line.clone());
+        lines.extend(self.waves());
+        lines.push(skip_line.clone());
+        if self.observations.iter().any(|obs| obs.swell.is_some()) {
+            lines.extend(self.component("Swell", |obs| obs.swell));
+            lines.push(skip_line.clone());
+        }
+        lines.extend(self.wind());
+        lines.push(skip_line);
+        lines.extend(self.water());
+        lines
+    }
+}
+
+impl<'a> Observations<'a> {
+    const LEGEND_WIDTH: usize = 11;
+    const BOUNDARY_WIDTH: usize = 1;
+    const MAX_COLUMNS: usize = 8;
+    const MIN_BIN_WIDTH: usize = 8;
+
+    /// Panics on empty observations
+    fn new(buoy: &'a Buoy, width: usize, charset: Charset) -> Self {
+        assert!(!buoy.observations.is_empty());
+
+        // Wave data is usually reported less often than the wind, so prefer
+        // showing the observations that include it.
+        let with_waves = buoy
+            .observations
+            .iter()
+            .filter(|obs| obs.wave_height.is_some())
+            .collect::<Vec<_>>();
+        let mut observations = if with_waves.is_empty() {
+            buoy.observations.iter().collect()
+        } else {
+            with_waves
+        };
+        let interior_width = width - 2;
+        let max_columns = Self::MAX_COLUMNS.min(max_columns(
+            interior_width,
+            Self::LEGEND_WIDTH,
+            Self::MIN_BIN_WIDTH,
+        ));
+        let skip = observations.len().saturating_sub(max_columns);
+        observations.drain(..skip);
+
+        let num_columns = observations.len();
+        let bin_width = (interior_width - num_columns * Self::BOUNDARY_WIDTH - Self::LEGEND_WIDTH)
+            / num_columns;
+        let used_space =
+            Self::LEGEND_WIDTH + num_columns * Self::BOUNDARY_WIDTH + num_columns * bin_width;
+        let right_margin = interior_width - used_space;
+
+        Self {
+            station: &buoy.station,
+            observations,
+            width,
+            bin_width,
+            right_margin,
+            charset,
+        }
+    }
+
+    /// Render a row with a legend and one cell per observation
+    fn row<F, S>(&self, legend: &str, cell: F) -> Line
+    where
+        F: Fn(&BuoyObservation) -> Option<S>,
+        S: Display,
+    {
+        let mut line = Vec::with_capacity(2 * self.observations.len() + 2);
+        line.push(label(legend, Self::LEGEND_WIDTH));
+        for obs in &self.observations {
+            line.push(span!("{:^width$}", "", width = Self::BOUNDARY_WIDTH));
+            let str = cell(obs).map_or_else(|| "-".to_owned(), |s| s.to_string());
+            line.push(span!("{:^width$}", str, width = self.bin_width));
+        }
+        line.push(span!("{:width$}", "", width = self.right_margin));
+        line
+    }
+
+    fn time(&self) -> Vec<Line> {
+        vec![self.row("Time", |obs| Some(obs.timestamp.format("%H:%M")))]
+    }
+
+    fn waves(&self) -> Vec<Line> {
+        vec![
+            self.row("", |obs| obs.wave_height.map(|h| format!("{h:.1} m"))),
+            self.row("Waves", |obs| {
+                obs.dominant_period.map(|p| format!("{p:.0}s"))
+            }),
+            self.row("", |obs| {
+                obs.mean_wave_direction
+                    .map(|degrees| direction(degrees, self.charset))
+            }),
+        ]
+    }
+
+    fn component<F>(&self, legend: &str, component: F) -> Vec<Line>
+    where
+        F: Fn(&BuoyObservation) -> Option<WaveComponent>,
+    {
+        vec![
+            self.row("", |obs| {
+                component(obs).map(|c| format!("{:.1} m", c.height))
+            }),
+            self.row(legend, |obs| {
+                component(obs).map(|c| format!("{:.0}s", c.period))
+            }),
+            self.row("", |obs| {
+                component(obs).map(|c| match self.charset {
+                    Charset::Unicode => format!(
+                        "{} {:?}",
+                        compass(c.compass_direction, self.charset),
+                        c.compass_direction
+                    ),
+                    // The arrow would only repeat the direction
+                    Charset::Ascii => format!("{:?}", c.compass_direction),
+                })
+            }),
+        ]
+    }
+
+    fn wind(&self) -> Vec<Line> {
+        vec![
+            self.row("", |obs| {
+                obs.wind_speed.map(|speed| match obs.gusts {
+                    Some(gusts) => format!("{speed:.0}-{gusts:.0} m/s"),
+                    None => format!("{speed:.0} m/s"),
+                })
+            }),
+            self.row("Wind", |obs| {
+                obs.wind_direction
+                    .map(|degrees| direction(degrees, self.charset))
+            }),
+        ]
+    }
+
+    fn water(&self) -> Vec<Line> {
+        vec![self.row("Water", |obs| {
+            obs.water_temperature
+                .map(|t| format!("{t:.1} {}C", self.charset.degrees()))
+        })]
+    }
+}
+
+/// Directions reported by NDBC are where the waves/wind come from
+fn direction(degrees: f32, charset: Charset) -> String {
+    bearing(CompassDirection::from_degrees(degrees), degrees, charset)
+}
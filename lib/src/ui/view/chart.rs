@@ -0,0 +1,38 @@
+//! Charts that renderers able to draw vectors show in place of their text
+
+use chrono::NaiveDateTime;
+
+use super::base::*;
+use crate::msw::forecast::{UnitLength, Wind};
+
+/// Swell height over a multi-day forecast, colored by rating
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chart {
+    pub title: String,
+    /// Top of the height axis
+    pub max_height: f32,
+    pub unit: UnitLength,
+    pub points: Vec<Point>,
+    /// The same chart drawn as text, for renderers that only draw text
+    pub text: Vec<Span>,
+}
+
+/// A single forecast time on the chart
+#[derive(Clone, Debug, PartialEq)]
+pub struct Point {
+    pub time: NaiveDateTime,
+    pub height: f32,
+    pub role: Role,
+    pub wind: Wind,
+}
+
+impl Chart {
+    /// Number of lines the text takes up
+    pub fn lines(&self) -> usize {
+        1 + self
+            .text
+            .iter()
+            .filter(|span| span.content == Content::Newline)
+            .count()
+    }
+}
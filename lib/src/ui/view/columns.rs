@@ -0,0 +1,108 @@
+//! Boxes with a column per time slot, shared by the comparison and diff views
+
+use chrono::NaiveDate;
+use itertools::Itertools;
+
+use super::base::*;
+use super::layout::max_columns;
+
+/// Widths within a box: a legend, then a bin per time slot, each after a one
+/// char boundary
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct Columns {
+    legend_width: usize,
+    bin_width: usize,
+    right_margin: usize,
+}
+
+impl Columns {
+    /// Split the interior evenly between `count` bins, leaving the remainder
+    /// as a right margin. Panics if `count` is zero.
+    pub(super) fn new(interior_width: usize, legend_width: usize, count: usize) -> Self {
+        assert!(count > 0);
+        let bin_width = (interior_width - count - legend_width) / count;
+        let used_space = legend_width + count + count * bin_width;
+        Self {
+            legend_width,
+            bin_width,
+            right_margin: interior_width - used_space,
+        }
+    }
+
+    /// Render a row with a legend and one cell per time slot, keeping the
+    /// cell's style
+    pub(super) fn row<T, F>(&self, legend: &str, slots: &[T], cell: F) -> Line
+    where
+        F: Fn(&T) -> Span,
+    {
+        let mut line = Vec::with_capacity(2 * slots.len() + 2);
+        line.push(label(legend, self.legend_width));
+        for slot in slots {
+            line.push(span!(" "));
+            let mut span = cell(slot);
+            if let Content::Text(text) = &span.content {
+                span.content = Content::Text(format!("{:^width$}", text, width = self.bin_width));
+            }
+            line.push(span);
+        }
+        line.push(span!("{:width$}", "", width = self.right_margin));
+        line
+    }
+}
+
+/// Group time slots by day, splitting days with more slots than fit
+/// `interior_width` across several boxes
+pub(super) fn day_boxes<T, F>(
+    slots: &[T],
+    date: F,
+    interior_width: usize,
+    legend_width: usize,
+    min_bin_width: usize,
+) -> Vec<(NaiveDate, Vec<&T>)>
+where
+    F: Fn(&T) -> NaiveDate,
+{
+    let columns = max_columns(interior_width, legend_width, min_bin_width);
+    let mut boxes = Vec::new();
+    for (date, slots) in &slots.iter().group_by(|slot| date(slot)) {
+        let slots = slots.collect::<Vec<_>>();
+        for slots in slots.chunks(columns) {
+            boxes.push((date, slots.to_vec()));
+        }
+    }
+    boxes
+}
+
+/// Title of a day's box, e.g. `Tue Jul 05`
+pub(super) fn day_title(date: NaiveDate) -> String {
+    date.format("%a %b %d").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_days_into_boxes() {
+        let day = |d| NaiveDate::from_ymd(2022, 7, d);
+        let slots = [(5, 0), (5, 3), (5, 6), (6, 0)];
+        let boxes = day_boxes(&slots, |slot| day(slot.0), 28, 11, 7);
+        let boxes = boxes
+            .into_iter()
+            .map(|(date, slots)| (date, slots.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(boxes, vec![(day(5), 2), (day(5), 1), (day(6), 1)]);
+
+        let columns = Columns::new(28, 11, 2);
+        let row = columns.row("Time", &slots[..2], |slot| span!("{}", slot.1));
+        let text = row
+            .into_iter()
+            .map(|span| match span.content {
+                Content::Text(text) => text,
+                _ => String::new(),
+            })
+            .collect::<String>();
+        assert_eq!(text.chars().count(), 28);
+        assert_eq!(text, "   Time        0       3    ");
+    }
+}
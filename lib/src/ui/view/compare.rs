@@ -0,0 +1,214 @@
+use chrono::NaiveDate;
+
+use super::base::*;
+use super::border::Border;
+use super::columns::{day_boxes, day_title, Columns};
+use super::forecast::{compass, height_range, stars_role};
+use super::layout::{truncate, Charset, Layout};
+use crate::msw::forecast::Forecast;
+use crate::rating::Rater;
+use crate::spot::Spot;
+
+/// Several spots' forecasts side by side, to pick between nearby breaks
+pub struct Comparison {
+    spots: Vec<(Spot, Vec<Forecast>)>,
+    /// Spots whose forecast couldn't be fetched, and why
+    errors: Vec<(String, String)>,
+}
+
+impl Comparison {
+    pub fn new() -> Self {
+        Self {
+            spots: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Add a spot's forecast. The first spot's times are the ones shown.
+    pub fn spot(mut self, spot: Spot, forecast: Vec<Forecast>) -> Self {
+        self.spots.push((spot, forecast));
+        self
+    }
+
+    /// Add a spot whose forecast couldn't be fetched, listed above the rest
+    pub fn error(mut self, label: impl Into<String>, error: impl ToString) -> Self {
+        self.errors.push((label.into(), error.to_string()));
+        self
+    }
+}
+
+impl Default for Comparison {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Layout for Comparison {
+    /// Transform the forecasts into stylized text snippets, one box per day.
+    /// Days with more time slots than fit the width are split across boxes.
+    fn layout(self, width: usize, charset: Charset) -> View {
+        let comparison = self;
+        let raters = comparison
+            .spots
+            .iter()
+            .map(|(spot, forecast)| Rater::for_spot(spot).range(forecast))
+            .collect::<Vec<_>>();
+        let mut spans = Vec::new();
+        for (label, error) in &comparison.errors {
+            let mut warning = Span::new(truncate(&format!("{label}: {error}"), width));
+            warning.style().role(Role::Warning);
+            spans.push(warning);
+            spans.push(Span::newline());
+        }
+        let times = match comparison.spots.first() {
+            Some((_, forecast)) if !forecast.is_empty() => forecast,
+            _ => {
+                spans.push(span!("No forecasts to compare"));
+                spans.push(Span::newline());
+                return View { spans };
+            }
+        };
+        let boxes = day_boxes(
+            times,
+            |fc| fc.local_timestamp.date(),
+            width - 2,
+            DayComparison::LEGEND_WIDTH,
+            DayComparison::MIN_BIN_WIDTH,
+        );
+        for (date, slots) in boxes {
+            if !spans.is_empty() {
+                spans.push(Span::newline());
+            }
+            spans.extend(
+                DayComparison::new(date, slots, &comparison.spots, &raters, width, charset).draw(),
+            );
+        }
+        View { spans }
+    }
+}
+
+/// A single day of every spot's forecast, one column per time slot
+struct DayComparison<'a> {
+    date: NaiveDate,
+    /// The first spot's forecast, for the times to show
+    slots: Vec<&'a Forecast>,
+    spots: &'a [(Spot, Vec<Forecast>)],
+    raters: &'a [Rater],
+    width: usize,
+    columns: Columns,
+    charset: Charset,
+}
+
+impl Border for DayComparison<'_> {
+    fn title(&self) -> String {
+        day_title(self.date)
+    }
+
+    // Rows:
+    //   Time
+    //   For each spot:
+    //     Name
+    //     Surf: Height, colored by rating
+    //     Period
+    //     Wind: Speed, Direction
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn draw_inner(&self) -> Vec<Line> {
+        let skip_line = vec![span!("{:^width$}", "", width = self.interior_width())];
+
+        let mut lines = vec![self.row("Time", |fc| {
+            Span::new(fc.local_timestamp.format("%l%P").to_string().trim())
+        })];
+        for ((spot, forecast), rater) in self.spots.iter().zip(self.raters) {
+            lines.push(skip_line.clone());
+            let width = self.interior_width() - 1;
+            let name = spot.name.chars().take(width).collect::<String>();
+            let mut name = span!(" {:<width$}", name);
+            name.style().bold();
+            lines.push(vec![name]);
+
+            // Look up each slot by time, in case the spots' forecasts start at
+            // different times
+            let find = |slot: &Forecast| forecast.iter().find(|fc| fc.timestamp == slot.timestamp);
+            lines.push(self.row("Surf", |slot| match find(slot) {
+                Some(fc) => {
+                    let stars = rater.rate(fc).stars;
+                    let mut span = Span::new(height_range(fc));
+                    span.style().role(stars_role(stars));
+                    span
+                }
+                None => span!("-"),
+            }));
+            lines.push(self.row("Period", |slot| {
+                match find(slot).and_then(|fc| fc.swell.components.primary) {
+                    Some(primary) => span!("{}s", primary.period),
+                    None => span!("-"),
+                }
+            }));
+            lines.push(self.row("Wind", |slot| match find(slot) {
+                Some(fc) => span!(
+                    "{} {}{}",
+                    compass(fc.wind.compass_direction, self.charset),
+                    fc.wind.speed,
+                    fc.wind.unit
+                ),
+                None => span!("-"),
+            }));
+        }
+        lines
+    }
+}
+
+impl<'a> DayComparison<'a> {
+    const LEGEND_WIDTH: usize = 11;
+    const MIN_BIN_WIDTH: usize = 8;
+
+    /// Panics on empty slots
+    fn new(
+        date: NaiveDate,
+        slots: Vec<&'a Forecast>,
+        spots: &'a [(Spot, Vec<Forecast>)],
+        raters: &'a [Rater],
+        width: usize,
+        charset: Charset,
+    ) -> Self {
+        let columns = Columns::new(width - 2, Self::LEGEND_WIDTH, slots.len());
+        Self {
+            date,
+            slots,
+            spots,
+            raters,
+            width,
+            columns,
+            charset,
+        }
+    }
+
+    fn row<F>(&self, legend: &str, cell: F) -> Line
+    where
+        F: Fn(&Forecast) -> Span,
+    {
+        self.columns.row(legend, &self.slots, |slot| cell(slot))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::{render_width, Plain};
+
+    #[test]
+    fn lists_spots_that_failed() {
+        let json = include_str!("../../../../test/msw/forecast.json");
+        let forecast: Vec<Forecast> = serde_json::from_str(json).unwrap();
+        let comparison = Comparison::new()
+            .spot(Spot::new(4203, "ormond-beach"), forecast)
+            .error("nowhere", "Spot not found");
+        let text = render_width(Plain, comparison, 40);
+        assert!(text.starts_with("nowhere: Spot not found\n"), "{text}");
+        assert!(text.contains("Ormond Beach"));
+        assert!(text.lines().all(|line| line.chars().count() <= 40));
+    }
+}
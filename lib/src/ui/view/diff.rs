@@ -0,0 +1,173 @@
+use chrono::NaiveDateTime;
+
+use super::base::*;
+use super::border::Border;
+use super::columns::{day_boxes, day_title, Columns};
+use super::layout::{wrap, Charset, Layout};
+use crate::diff::{Change, RunDiff, SlotDiff, Trend};
+use crate::rating::Level;
+
+impl Layout for RunDiff {
+    /// Transform the changes between two runs into stylized text snippets.
+    /// Days with more time slots than fit the width are split across boxes.
+    fn layout(self, width: usize, _charset: Charset) -> View {
+        let diff = self;
+        let mut spans = Vec::new();
+        let mut paragraph = |text: String| {
+            for line in wrap(text.split(' ').map(str::to_owned), " ", width) {
+                spans.push(Span::new(line));
+                spans.push(Span::newline());
+            }
+        };
+        paragraph(format!(
+            "Changes since the forecast issued {} UTC",
+            issued(diff.before_issued)
+        ));
+        if diff.slots.is_empty() {
+            paragraph("No forecast times in common with that run".to_owned());
+        }
+        let boxes = day_boxes(
+            &diff.slots,
+            |slot| slot.local_timestamp.date(),
+            width - 2,
+            DayDiff::LEGEND_WIDTH,
+            DayDiff::MIN_BIN_WIDTH,
+        );
+        for (_, slots) in boxes {
+            spans.push(Span::newline());
+            spans.extend(DayDiff::new(&diff, slots, width).draw());
+        }
+        View { spans }
+    }
+}
+
+/// The changes for a single day, one column per time slot
+struct DayDiff<'a> {
+    diff: &'a RunDiff,
+    slots: Vec<&'a SlotDiff>,
+    width: usize,
+    columns: Columns,
+}
+
+impl Border for DayDiff<'_> {
+    fn title(&self) -> String {
+        day_title(self.slots[0].local_timestamp.date())
+    }
+
+    // Rows:
+    //   Time
+    //   Swell: Height, Change
+    //   Period: Period, Change
+    //   Wind: Speed, Change
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn draw_inner(&self) -> Vec<Line> {
+        let skip_line = vec![span!("{:^width$}", "", width = self.interior_width())];
+        let length = self.diff.unit_length;
+        let speed = self.diff.unit_speed;
+
+        let mut lines = vec![self.row("Time", |slot| {
+            Span::new(slot.local_timestamp.format("%l%P").to_string().trim())
+        })];
+        lines.push(skip_line.clone());
+        lines.push(self.row("Swell", |slot| span!("{}{length}", slot.height.after)));
+        lines.push(self.row("", |slot| change(&slot.height, 1)));
+        if self.slots.iter().any(|slot| slot.period.is_some()) {
+            lines.push(skip_line.clone());
+            lines.push(self.row("Period", |slot| match slot.period {
+                Some(period) => span!("{}s", period.after),
+                None => span!("-"),
+            }));
+            lines.push(self.row("", |slot| match &slot.period {
+                Some(period) => change(period, 0),
+                None => span!(""),
+            }));
+        }
+        lines.push(skip_line);
+        lines.push(self.row("Wind", |slot| span!("{}{speed}", slot.wind.after)));
+        lines.push(self.row("", |slot| change(&slot.wind, 0)));
+        lines
+    }
+}
+
+impl<'a> DayDiff<'a> {
+    const LEGEND_WIDTH: usize = 11;
+    const MIN_BIN_WIDTH: usize = 7;
+
+    /// Panics on empty slots
+    fn new(diff: &'a RunDiff, slots: Vec<&'a SlotDiff>, width: usize) -> Self {
+        let columns = Columns::new(width - 2, Self::LEGEND_WIDTH, slots.len());
+        Self {
+            diff,
+            slots,
+            width,
+            columns,
+        }
+    }
+
+    fn row<F>(&self, legend: &str, cell: F) -> Line
+    where
+        F: Fn(&SlotDiff) -> Span,
+    {
+        self.columns.row(legend, &self.slots, |slot| cell(slot))
+    }
+}
+
+/// Signed change, styled as a good rating when it's an upgrade and a poor one
+/// when it's a downgrade
+fn change<T>(change: &Change<T>, decimals: usize) -> Span
+where
+    T: Copy + Into<f64>,
+{
+    let mut span = match change.trend {
+        Trend::Same => span!("·"),
+        _ => span!("{:+.decimals$}", change.delta()),
+    };
+    match change.trend {
+        Trend::Upgrade => {
+            span.style().role(Role::Rating(Level::Good));
+        }
+        Trend::Downgrade => {
+            span.style().role(Role::Rating(Level::Poor));
+        }
+        Trend::Same => {
+            span.style().role(Role::Muted);
+        }
+    }
+    span
+}
+
+fn issued(timestamp: i64) -> String {
+    NaiveDateTime::from_timestamp(timestamp, 0)
+        .format("%a %b %d %H:%M")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archive::Run;
+    use crate::ui::{render_width, Plain, MIN_WIDTH};
+
+    #[test]
+    fn fits_narrow_widths() {
+        let json = include_str!("../../../../test/msw/forecast.json");
+        let run = |issued| Run {
+            issued,
+            forecast: serde_json::from_str(json).unwrap(),
+        };
+        let diff = RunDiff::new(&run(1656979200), &run(1657000800));
+        let output = render_width(Plain, diff, MIN_WIDTH);
+        assert!(output.starts_with("Changes since the forecast issued Tue\nJul 05 00:00 UTC\n"));
+        for line in output.lines() {
+            assert!(line.chars().count() <= MIN_WIDTH, "{line}");
+        }
+
+        let mut after = run(1657000800);
+        after.forecast.clear();
+        let output = render_width(Plain, RunDiff::new(&run(1656979200), &after), MIN_WIDTH);
+        assert!(output.lines().all(|line| line.chars().count() <= MIN_WIDTH));
+    }
+}
@@ -0,0 +1,191 @@
+use chrono::Timelike;
+
+use super::base::*;
+use super::border::Border;
+use super::forecast::{compass, height_range, stars_role, stars_str};
+use super::layout::{truncate, Charset, Layout, DEFAULT_WIDTH};
+use crate::msw::forecast::Forecast;
+use crate::rating::Rater;
+use crate::spot::Spot;
+
+/// Local hours worth surfing, when looking for the best upcoming session
+const DAYTIME_HOURS: std::ops::RangeInclusive<u32> = 6..=18;
+
+/// A one line summary per favorite spot: conditions now, and the best rated
+/// daytime session coming up
+pub struct Favorites {
+    now: i64,
+    rows: Vec<Row>,
+    width: usize,
+    charset: Charset,
+}
+
+struct Row {
+    label: String,
+    forecast: Result<(Spot, Vec<Forecast>), String>,
+}
+
+impl Favorites {
+    /// Summarize conditions as of the `now` unix timestamp
+    pub fn new(now: i64) -> Self {
+        Self {
+            now,
+            rows: Vec::new(),
+            width: DEFAULT_WIDTH,
+            charset: Charset::default(),
+        }
+    }
+
+    /// Add a spot's forecast, labeled by its alias or name
+    pub fn spot(mut self, label: impl Into<String>, spot: Spot, forecast: Vec<Forecast>) -> Self {
+        self.rows.push(Row {
+            label: label.into(),
+            forecast: Ok((spot, forecast)),
+        });
+        self
+    }
+
+    /// Add a spot whose forecast couldn't be fetched
+    pub fn error(mut self, label: impl Into<String>, error: impl ToString) -> Self {
+        self.rows.push(Row {
+            label: label.into(),
+            forecast: Err(error.to_string()),
+        });
+        self
+    }
+}
+
+impl Layout for Favorites {
+    fn layout(self, width: usize, charset: Charset) -> View {
+        View {
+            spans: Self {
+                width,
+                charset,
+                ..self
+            }
+            .draw(),
+        }
+    }
+}
+
+impl Border for Favorites {
+    fn title(&self) -> String {
+        "Favorites".to_owned()
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    // Narrower views drop the best session, and then the swell
+    fn draw_inner(&self) -> Vec<Line> {
+        const LABEL_WIDTH: usize = 20;
+        const MIN_LABEL_WIDTH: usize = 10;
+        const SURF_WIDTH: usize = 9;
+        const SWELL_WIDTH: usize = 9;
+        const WIND_WIDTH: usize = 10;
+        const RATING_WIDTH: usize = 7;
+        const MIN_BEST_WIDTH: usize = 18;
+        let interior = self.interior_width();
+        let fixed = SURF_WIDTH + WIND_WIDTH + RATING_WIDTH;
+        let (swell_width, best_width) =
+            if interior >= LABEL_WIDTH + fixed + SWELL_WIDTH + MIN_BEST_WIDTH {
+                (SWELL_WIDTH, interior - LABEL_WIDTH - fixed - SWELL_WIDTH)
+            } else if interior >= MIN_LABEL_WIDTH + fixed + SWELL_WIDTH {
+                (SWELL_WIDTH, 0)
+            } else {
+                (0, 0)
+            };
+        let label_width = interior - fixed - swell_width - best_width;
+        let best_header = if best_width > 0 { "Best daytime" } else { "" };
+
+        let mut lines = vec![vec![span!(
+            "{:label_width$}{:^SURF_WIDTH$}{:^swell_width$}{:^WIND_WIDTH$}{:^RATING_WIDTH$}{:^best_width$}",
+            "",
+            "Surf",
+            if swell_width > 0 { "Swell" } else { "" },
+            "Wind",
+            "Now",
+            best_header
+        )]];
+        for Row { label, forecast } in &self.rows {
+            let label = span!(
+                " {:<width$}",
+                truncate(label, label_width - 2),
+                width = label_width - 1
+            );
+            let (spot, forecast) = match forecast {
+                Ok((spot, forecast)) if !forecast.is_empty() => (spot, forecast),
+                Ok(_) => {
+                    let rest = interior - label_width;
+                    lines.push(vec![label, span!("{:<rest$}", "No forecast")]);
+                    continue;
+                }
+                Err(e) => {
+                    let rest = interior - label_width;
+                    let mut error = span!("{:<rest$}", truncate(e, rest));
+                    error.style().role(Role::Warning);
+                    lines.push(vec![label, error]);
+                    continue;
+                }
+            };
+            let rater = Rater::for_spot(spot).range(forecast);
+            // The slot in progress, or the first one if they're all upcoming
+            let current = forecast
+                .iter()
+                .rposition(|fc| fc.timestamp <= self.now)
+                .unwrap_or(0);
+            let fc = &forecast[current];
+            let swell = match fc.swell.components.primary {
+                Some(c) if swell_width > 0 => {
+                    format!(
+                        "{} {}s",
+                        compass(c.compass_direction, self.charset),
+                        c.period
+                    )
+                }
+                _ => String::new(),
+            };
+            let wind = format!(
+                "{} {}{}",
+                compass(fc.wind.compass_direction, self.charset),
+                fc.wind.speed,
+                fc.wind.unit
+            );
+            let stars = rater.rate(fc).stars;
+            let mut now = span!("{:^RATING_WIDTH$}", stars_str(stars));
+            now.style().role(stars_role(stars));
+
+            let best = forecast[current..]
+                .iter()
+                .filter(|fc| DAYTIME_HOURS.contains(&fc.local_timestamp.hour()))
+                .map(|fc| (fc, rater.rate(fc).stars))
+                // First of the highest rated
+                .rev()
+                .max_by_key(|(_, stars)| *stars);
+            let best = match best {
+                Some((fc, stars)) if best_width > 0 => {
+                    let time = fc.local_timestamp.format("%a %l%P").to_string();
+                    let mut best = span!(
+                        "  {:<width$}",
+                        format!("{} {}", time.replace("  ", " "), stars_str(stars)),
+                        width = best_width - 2
+                    );
+                    best.style().role(stars_role(stars));
+                    best
+                }
+                _ => span!("{:best_width$}", ""),
+            };
+
+            lines.push(vec![
+                label,
+                span!("{:^SURF_WIDTH$}", height_range(fc)),
+                span!("{:^swell_width$}", swell),
+                span!("{:^WIND_WIDTH$}", wind),
+                now,
+                best,
+            ]);
+        }
+        lines
+    }
+}
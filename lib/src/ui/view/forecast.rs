@@ -1,26 +1,111 @@
 use std::{cmp::Ordering, fmt::Display};
 
-use chrono::Timelike;
+use chrono::{NaiveDate, NaiveDateTime, Timelike};
 use itertools::Itertools;
 
 use super::base::*;
+use super::border::Border;
+use super::chart::{Chart, Point};
+use super::layout::{wrap, Charset, Layout};
+use super::table::Table;
 use crate::msw::forecast::{
-    CompassDirection, Forecast, SwellComponent, SwellComponents, UnitLength,
+    CompassDirection, Forecast, SwellComponent, SwellComponents, UnitLength, UnitTemperature,
 };
+use crate::rating::{Level, Rater, MAX_STARS};
+use crate::shore::{SwellExposure, WindShore};
+use crate::spot::{Coordinates, Spot};
+use crate::sun::SunTimes;
+use crate::tide::{Station, TideKind};
 
-/// Total width of the viewpoint output. If the user's viewpoint is smaller than
-/// this, output will look choppy, so we want to minimize it while keeping the
-/// view useful.
-const VIEWPOINT_WIDTH: usize = 90;
-/// Viewpoint width minus the border chars
-const INTERIOR_VIEWPOINT_WIDTH: usize = VIEWPOINT_WIDTH - 2;
+/// A spot's forecast, along with any supplementary data to display with it
+pub struct Report {
+    forecast: Vec<Forecast>,
+    tides: Option<Station>,
+    coordinates: Option<Coordinates>,
+    bearing: Option<f32>,
+    rater: Option<Rater>,
+}
+
+impl Report {
+    pub fn new(forecast: Vec<Forecast>) -> Self {
+        Self {
+            forecast,
+            tides: None,
+            coordinates: None,
+            bearing: None,
+            rater: None,
+        }
+    }
+
+    /// Show everything known about the spot: tides, daylight, wind and swell
+    /// relative to the shore, and ratings against its profile
+    pub fn for_spot(forecast: Vec<Forecast>, spot: &Spot) -> Self {
+        let rater = Rater::for_spot(spot).range(&forecast);
+        let mut report = Self::new(forecast).rating(rater);
+        report.tides = spot.tides();
+        report.coordinates = spot.coordinates;
+        report.bearing = spot.bearing;
+        report
+    }
+
+    /// Show tide predictions from the given station
+    pub fn tides(mut self, station: Station) -> Self {
+        self.tides = Some(station);
+        self
+    }
+
+    /// Show daylight times for the spot's location
+    pub fn daylight(mut self, coordinates: Coordinates) -> Self {
+        self.coordinates = Some(coordinates);
+        self
+    }
 
-impl From<Vec<Forecast>> for View {
+    /// Judge wind and swell relative to a shore with the given beach-facing
+    /// bearing
+    pub fn shore(mut self, bearing: f32) -> Self {
+        self.bearing = Some(bearing);
+        self
+    }
+
+    /// Rate the forecast with our own ratings, rather than the provider's
+    pub fn rating(mut self, rater: Rater) -> Self {
+        self.rater = Some(rater);
+        self
+    }
+}
+
+impl Layout for Vec<Forecast> {
+    fn layout(self, width: usize, charset: Charset) -> View {
+        Report::new(self).layout(width, charset)
+    }
+}
+
+impl Layout for Report {
     /// Transform a forecast into stylized text snippets
-    fn from(forecast: Vec<Forecast>) -> Self {
+    fn layout(self, width: usize, charset: Charset) -> View {
+        let report = self;
+        let forecast = report.forecast;
+        // Show tides in the same units as the swell
+        let tides = match (report.tides, forecast.first()) {
+            (Some(station), Some(fc)) => Some(station.in_unit(fc.swell.unit)),
+            _ => None,
+        };
+
+        let daylight = Daylight::new(forecast.as_slice(), report.coordinates);
+        let context = Context {
+            tides: tides.as_ref(),
+            daylight: daylight.as_ref(),
+            bearing: report.bearing,
+            rater: report.rater.as_ref(),
+            width,
+            charset,
+        };
+
         let mut spans = Vec::new();
         // Graph is uninteresting by day, so make it the full week
-        spans.extend(Graph::new(forecast.as_slice()).draw());
+        spans.push(Span::chart(
+            Graph::new(forecast.as_slice(), context).chart(),
+        ));
 
         // This may be fragile; assumes 12am,3,6,9,12,3,6,9pm for each day
         // Could probably partition by datetime.day value
@@ -28,103 +113,91 @@ impl From<Vec<Forecast>> for View {
         for fc in days {
             if !fc.is_empty() {
                 spans.push(Span::newline());
-                spans.extend(Day::new(fc).draw());
+                spans.push(Span::table(Day::new(fc, context).table()));
             }
         }
-        Self { spans }
+        View { spans }
     }
 }
 
-/// TODO maybe move base stuff to internal mod
-// Also: make a more elegant abstraction than this?
-trait Border {
-    /// Title string (unpadded)
-    fn title(&self) -> String;
-
-    /// Contents within the border
-    fn draw_inner(&self) -> Vec<Line>;
+/// Supplementary data to display alongside the forecast, and the width and
+/// characters to display it with
+#[derive(Clone, Copy)]
+pub struct Context<'a> {
+    tides: Option<&'a Station>,
+    daylight: Option<&'a Daylight>,
+    bearing: Option<f32>,
+    rater: Option<&'a Rater>,
+    width: usize,
+    charset: Charset,
+}
 
-    /// Default `draw()` is to border the contents within the border.
-    fn draw(self) -> Vec<Span>
-    where
-        Self: Sized,
-    {
-        self.border(self.draw_inner())
+impl Context<'_> {
+    /// Rate by our own rating when available. Otherwise, when the shore's
+    /// bearing is known, rate by the wind relative to the shore, and flag
+    /// swell that can't reach the beach. Failing that, just use the provider's
+    /// star rating as a proxy. Hours of darkness are marked as such.
+    fn role(&self, fc: &Forecast) -> Role {
+        if self.daylight.is_some_and(|daylight| daylight.is_dark(fc)) {
+            return Role::Night;
+        }
+        if let Some(rater) = self.rater {
+            return Role::Rating(rater.rate(fc).level);
+        }
+        if let Some(bearing) = self.bearing {
+            let blocked = fc.swell.components.primary.is_some_and(|primary| {
+                SwellExposure::classify(bearing, &primary) == SwellExposure::Blocked
+            });
+            if blocked {
+                return Role::Rating(Level::Poor);
+            }
+            return wind_role(WindShore::classify(bearing, &fc.wind));
+        }
+        match (fc.solid_rating, fc.faded_rating) {
+            (0, _) => Role::Rating(Level::Poor),
+            (_, 0) => Role::Rating(Level::Good),
+            (_, _) => Role::Rating(Level::Fair),
+        }
     }
+}
 
-    /// Wrap inner view with a titled border.
-    fn border(&self, inner: Vec<Line>) -> Vec<Span> {
-        // Top border of the view manually handles border offsets
-        let mut spans = self.border_top();
-        spans.push(Span::newline());
-
-        // Wrap border around each interior line
-        let border_wrap = [Span::new(LINE_VERT), Span::newline(), Span::new(LINE_VERT)];
-        spans.push(Span::new(LINE_VERT));
-        spans.extend(inner.as_slice().join(&border_wrap[..]));
-        spans.push(Span::new(LINE_VERT));
-        spans.push(Span::newline());
-
-        // Bottom border of the view manually handles border offsets
-        spans.extend(self.border_bottom());
+/// Sun times for each local date in a forecast
+pub struct Daylight(Vec<(NaiveDate, SunTimes)>);
 
-        spans
+impl Daylight {
+    fn new(forecast: &[Forecast], coordinates: Option<Coordinates>) -> Option<Self> {
+        let coordinates = coordinates?;
+        let days = forecast
+            .iter()
+            .map(|fc| fc.local_timestamp.date())
+            .dedup()
+            .map(|date| (date, SunTimes::new(coordinates, date)))
+            .collect();
+        Some(Self(days))
     }
 
-    /// Render border top title
-    fn border_top(&self) -> Vec<Span> {
-        let title = format!(" {} ", self.title());
-        // top line
-        let box_top = format!(
-            "{CORNER_TOP_LEFT}{:─^width$}{CORNER_TOP_RIGHT}",
-            "",
-            width = title.len()
-        );
-        let top = format!("{:^width$}", box_top, width = VIEWPOINT_WIDTH);
-        // middle line
-        let box_mid = format!("{TEE_LEFT}{title}{TEE_RIGHT}");
-        let mid = format!(
-            "{CORNER_TOP_LEFT}{:─^width$}{CORNER_TOP_RIGHT}",
-            box_mid,
-            width = INTERIOR_VIEWPOINT_WIDTH
-        );
-        // bottom line
-        let box_btm = format!(
-            "{CORNER_BTM_LEFT}{:─^width$}{CORNER_BTM_RIGHT}",
-            "",
-            width = title.len()
-        );
-        let btm = format!(
-            "{LINE_VERT}{:^width$}{LINE_VERT}",
-            box_btm,
-            width = INTERIOR_VIEWPOINT_WIDTH
-        );
-        vec![
-            Span::new(top),
-            Span::newline(),
-            Span::new(mid),
-            Span::newline(),
-            Span::new(btm),
-        ]
+    fn get(&self, date: NaiveDate) -> Option<&SunTimes> {
+        self.0
+            .iter()
+            .find(|(d, _)| *d == date)
+            .map(|(_, times)| times)
     }
 
-    /// Closing for the bottom of the border box
-    fn border_bottom(&self) -> Vec<Span> {
-        vec![span!(
-            "{CORNER_BTM_LEFT}{:─^width$}{CORNER_BTM_RIGHT}",
-            "",
-            width = INTERIOR_VIEWPOINT_WIDTH
-        )]
+    /// Whether it is dark at the time of the forecast
+    fn is_dark(&self, fc: &Forecast) -> bool {
+        self.get(fc.local_timestamp.date())
+            .is_some_and(|times| times.is_dark(fc.timestamp))
     }
 }
 
 /// The swell graph over a multi-day forecast
-// TODO: gray out 6pm - 6am and add 6hr-x-axis ticks
+// TODO: add 6hr-x-axis ticks
 struct Graph<'a> {
     forecast: &'a [Forecast],
     min_swell_height: f32,
     max_swell_height: f32,
     midnight: &'a Forecast,
+    context: Context<'a>,
 }
 
 impl Border for Graph<'_> {
@@ -147,38 +220,59 @@ impl Border for Graph<'_> {
         format!("{date_init} - {date_end}")
     }
 
+    fn width(&self) -> usize {
+        self.context.width
+    }
+
     fn draw_inner(&self) -> Vec<Line> {
         let (legend_bin, legend_width) = self.legend_column();
-        let num_bins = self.forecast.len();
+        // Skip forecasts evenly if there isn't room for a bin and boundary each
+        let max_bins = (self.interior_width() - legend_width).div_ceil(2);
+        let step = self.forecast.len().div_ceil(max_bins);
+        let forecast = self.forecast.iter().step_by(step).collect::<Vec<_>>();
+        let num_bins = forecast.len();
         let num_bin_boundaries = num_bins - 1;
-        let bin_width = (INTERIOR_VIEWPOINT_WIDTH - legend_width - num_bin_boundaries) / num_bins;
+        let bin_width = (self.interior_width() - legend_width - num_bin_boundaries) / num_bins;
         let used_space = legend_width + num_bin_boundaries + num_bins * bin_width;
-        let right_margin = INTERIOR_VIEWPOINT_WIDTH - used_space;
+        let right_margin = self.interior_width() - used_space;
 
         // Initialize with blank spans of the correct width
         let mut bins = vec![
-            vec![span!("{:width$}", "", width = bin_width); num_bins];
+            vec![vec![span!("{:width$}", "", width = bin_width)]; num_bins];
             Self::SWELL_GRAPH_HEIGHT
         ];
+        let tide_rows = self.tide_rows(&forecast);
         let mut boundaries =
             vec![vec![Span::new(" "); num_bin_boundaries]; Self::SWELL_GRAPH_HEIGHT];
 
         let mut last_height = None;
         for x in 0..num_bins {
-            let fc = &self.forecast[x];
+            let fc = forecast[x];
             // TODO height is reversed; maybe assemble graph bottom up?
             let height = Self::SWELL_GRAPH_HEIGHT - self.scale(fc.swell.abs_max_breaking_height);
-            let color = Self::color(fc);
+            let role = self.context.role(fc);
 
             // Fill in bin
             for (y, bin_line) in bins.iter_mut().enumerate() {
-                let span = &mut bin_line[x];
-                match height.cmp(&y) {
-                    Ordering::Equal => *span = span!("{:─^width$}", "", width = bin_width),
-                    Ordering::Less => *span = span!("{:.^width$}", "", width = bin_width),
-                    _ => {}
+                let fill = match height.cmp(&y) {
+                    Ordering::Equal => LINE_HORIZONTAL,
+                    Ordering::Less => ".",
+                    _ => " ",
+                };
+                let mut span = span!("{}", fill.repeat(bin_width));
+                span.style().role(role);
+                bin_line[x] = match tide_rows.as_ref().map(|rows| rows[x]) {
+                    // Overlay the tide level in the middle of the bin
+                    Some(tide_y) if tide_y == y => {
+                        let left = (bin_width - 1) / 2;
+                        let mut left_span = span!("{}", fill.repeat(left));
+                        let mut right_span = span!("{}", fill.repeat(bin_width - 1 - left));
+                        left_span.style().role(role);
+                        right_span.style().role(role);
+                        vec![left_span, Span::new(TIDE_MARKER), right_span]
+                    }
+                    _ => vec![span],
                 };
-                span.style().fg(color);
             }
             // Fill in left-side boundary
             if let Some(last_height) = last_height {
@@ -207,7 +301,7 @@ impl Border for Graph<'_> {
                         }
                         (Ordering::Less, _, Ordering::Equal) => *span = Span::new(CORNER_BTM_RIGHT),
                     }
-                    span.style().fg(color);
+                    span.style().role(role);
                 }
             }
 
@@ -217,7 +311,11 @@ impl Border for Graph<'_> {
         let mut lines = Vec::new();
         for ((legend, bin), boundary) in legend_bin.into_iter().zip(bins).zip(boundaries) {
             let mut line: Vec<Span> = vec![legend];
-            line.extend(bin.into_iter().interleave(boundary));
+            line.extend(
+                bin.into_iter()
+                    .interleave(boundary.into_iter().map(|span| vec![span]))
+                    .flatten(),
+            );
             line.push(span!("{:width$}", "", width = right_margin));
             lines.push(line);
         }
@@ -229,7 +327,7 @@ impl<'a> Graph<'a> {
     const SWELL_GRAPH_HEIGHT: usize = 10;
 
     /// Panics on empty forecast
-    pub fn new(forecast: &'a [Forecast]) -> Self {
+    pub fn new(forecast: &'a [Forecast], context: Context<'a>) -> Self {
         assert!(!forecast.is_empty());
         let buffer = match forecast.first().unwrap().swell.unit {
             UnitLength::Feet => 1.0,
@@ -247,6 +345,28 @@ impl<'a> Graph<'a> {
             min_swell_height,
             max_swell_height,
             midnight,
+            context,
+        }
+    }
+
+    /// The graph as a chart, carrying the text drawing along with it
+    fn chart(self) -> Chart {
+        let points = self
+            .forecast
+            .iter()
+            .map(|fc| Point {
+                time: fc.local_timestamp,
+                height: fc.swell.abs_max_breaking_height,
+                role: self.context.role(fc),
+                wind: fc.wind,
+            })
+            .collect();
+        Chart {
+            title: self.title(),
+            max_height: self.max_swell_height,
+            unit: self.midnight.swell.unit,
+            points,
+            text: self.draw(),
         }
     }
 
@@ -275,30 +395,58 @@ impl<'a> Graph<'a> {
             vec![span!("{:width$}", "", width = legend_width); Self::SWELL_GRAPH_HEIGHT];
         legend_bin[0] = Span::new(legend_max);
         legend_bin[Self::SWELL_GRAPH_HEIGHT - 1] = Span::new(legend_min);
+        if self.context.tides.is_some() {
+            let tide_legend = format!("{TIDE_MARKER}tide");
+            if tide_legend.chars().count() <= legend_width {
+                legend_bin[Self::SWELL_GRAPH_HEIGHT / 2] =
+                    span!("{:^width$}", tide_legend, width = legend_width);
+            }
+        }
+        for span in legend_bin.iter_mut() {
+            span.style().role(Role::Axis);
+        }
         (legend_bin, legend_width)
     }
 
+    /// Graph row of the tide level for each bin, scaled to the week's tidal
+    /// range. Assumes 0 is the top of the graph.
+    fn tide_rows(&self, forecast: &[&Forecast]) -> Option<Vec<usize>> {
+        let station = self.context.tides?;
+        let heights = forecast
+            .iter()
+            .map(|fc| station.height(fc.timestamp))
+            .collect::<Vec<_>>();
+        let (min, max) = heights
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), h| {
+                (lo.min(*h), hi.max(*h))
+            });
+        let top = (Self::SWELL_GRAPH_HEIGHT - 1) as f32;
+        let rows = heights
+            .into_iter()
+            .map(|h| {
+                let proportion = if max > min {
+                    (h - min) / (max - min)
+                } else {
+                    0.5
+                };
+                (top - proportion * top).round() as usize
+            })
+            .collect();
+        Some(rows)
+    }
+
     fn scale(&self, height: f32) -> usize {
         let swell_range = self.max_swell_height - self.min_swell_height;
         let proportion_of_range = (height - self.min_swell_height) / swell_range;
         let scaled_to_graph = proportion_of_range * Self::SWELL_GRAPH_HEIGHT as f32;
         scaled_to_graph.round() as usize
     }
-
-    /// The logic for coloring is actually quite limited; we don't have spot
-    /// data for directions to determine whether or not swell/wind is
-    /// on/off/cross-shore. Just using star rating as a proxy.
-    fn color(fc: &Forecast) -> Color {
-        match (fc.solid_rating, fc.faded_rating) {
-            (0, _) => Color::Red,
-            (_, 0) => Color::Green,
-            (_, _) => Color::Blue,
-        }
-    }
 }
 
 pub struct Day<'a> {
     forecast: &'a [Forecast],
+    context: Context<'a>,
     bin_width: usize,
     right_margin: usize,
 }
@@ -316,20 +464,36 @@ impl Border for Day<'_> {
         format!("{date}")
     }
 
+    fn width(&self) -> usize {
+        self.context.width
+    }
+
     // Rows:
     //   Time
+    //   Rating (if rated)
     //   Swell (primary, secondary if present)
     //     Height, Direction, Arrow, Period
     //   Wind
-    //     Arrow, Dir, Speed
+    //     Arrow, Dir, Speed, Relative to shore (if bearing known)
     //   Weather
     //     Air temp
+    //   Daylight (if location known)
+    //     First light, sunrise, sunset, last light
+    //   Tide (if station known)
+    //     Height & trend, High/Low times
     // Columns: 3hr intervals
+    //
+    // Too narrow for a column per interval, the rows and columns swap; see
+    // `vertical`.
     fn draw_inner(&self) -> Vec<Line> {
-        let skip_line = vec![span!("{:^width$}", "", width = INTERIOR_VIEWPOINT_WIDTH)];
+        if self.bin_width < Self::MIN_BIN_WIDTH {
+            return self.vertical();
+        }
+        let skip_line = vec![span!("{:^width$}", "", width = self.interior_width())];
 
         let mut lines = vec![];
         lines.extend(self.time());
+        lines.extend(self.rating());
         lines.push(skip_line.clone());
         lines.extend(self.primary_swell());
         lines.push(skip_line.clone());
@@ -338,8 +502,16 @@ impl Border for Day<'_> {
             lines.push(skip_line.clone());
         }
         lines.extend(self.wind());
-        lines.push(skip_line);
+        lines.push(skip_line.clone());
         lines.extend(self.weather());
+        if self.context.daylight.is_some() {
+            lines.push(skip_line.clone());
+            lines.extend(self.daylight());
+        }
+        if self.context.tides.is_some() {
+            lines.push(skip_line);
+            lines.extend(self.tide());
+        }
         lines
     }
 }
@@ -348,35 +520,72 @@ impl<'a> Day<'a> {
     // Primary / Secondary / Wind / Weather
     const LEGEND_WIDTH: usize = 11;
     const BOUNDARY_WIDTH: usize = 1;
+    /// Narrowest column that fits each row's values, e.g. `10.5 ft`
+    const MIN_BIN_WIDTH: usize = 7;
 
     /// Panics on empty forecast
-    pub fn new(forecast: &'a [Forecast]) -> Self {
+    pub fn new(forecast: &'a [Forecast], context: Context<'a>) -> Self {
         assert!(!forecast.is_empty());
 
         // should be 8
         let num_forecasts = forecast.len();
         // between each forecast, and between legend and first forecast
         let num_boundaries = num_forecasts;
-        let bin_width =
-            (INTERIOR_VIEWPOINT_WIDTH - num_boundaries * Self::BOUNDARY_WIDTH - Self::LEGEND_WIDTH)
-                / num_forecasts;
+        let interior_width = context.width - 2;
+        let bin_width = interior_width
+            .saturating_sub(num_boundaries * Self::BOUNDARY_WIDTH + Self::LEGEND_WIDTH)
+            / num_forecasts;
         let used_space =
             Self::LEGEND_WIDTH + num_boundaries * Self::BOUNDARY_WIDTH + num_forecasts * bin_width;
-        let right_margin = INTERIOR_VIEWPOINT_WIDTH - used_space;
+        let right_margin = interior_width.saturating_sub(used_space);
 
         Self {
             forecast,
+            context,
             bin_width,
             right_margin,
         }
     }
 
+    /// The day as a table, one row per interval with every column, carrying
+    /// the text drawing along with it
+    fn table(self) -> Table {
+        let (header, rows) = {
+            let columns = self.columns(usize::MAX);
+            let header = columns
+                .iter()
+                .map(|column| column.header.to_owned())
+                .collect();
+            let rows = self
+                .forecast
+                .iter()
+                .map(|fc| columns.iter().map(|column| (column.cell)(fc)).collect())
+                .collect();
+            (header, rows)
+        };
+        let notes = [
+            ("Daylight", self.daylight_times()),
+            ("Tide", self.tide_extremes()),
+        ]
+        .into_iter()
+        .filter(|(_, parts)| !parts.is_empty())
+        .map(|(legend, parts)| format!("{legend}: {}", parts.join(", ")))
+        .collect();
+        Table {
+            title: self.title(),
+            header,
+            rows,
+            notes,
+            text: self.draw(),
+        }
+    }
+
     fn time(&self) -> Vec<Line> {
         // u23F2
         let mut time = Vec::with_capacity(2 * self.forecast.len() + 2);
 
         // Render the legend ( \u{23F2} for clock )
-        time.push(span!("{:^width$}", "Time", width = Self::LEGEND_WIDTH));
+        time.push(label("Time", Self::LEGEND_WIDTH));
 
         // Render each timestamp forecast
         for fc in self.forecast {
@@ -394,6 +603,25 @@ impl<'a> Day<'a> {
         vec![time]
     }
 
+    fn rating(&self) -> Vec<Line> {
+        let rater = match self.context.rater {
+            Some(rater) => rater,
+            None => return vec![],
+        };
+        let mut rating = Vec::with_capacity(2 * self.forecast.len() + 2);
+        rating.push(span!("{:^width$}", "", width = Self::LEGEND_WIDTH));
+        for fc in self.forecast {
+            rating.push(self.boundary());
+            let stars = rater.rate(fc).stars;
+            let mut span = span!("{:^width$}", stars_str(stars), width = self.bin_width);
+            span.style().role(stars_role(stars));
+            rating.push(span);
+        }
+        rating.push(span!("{:width$}", "", width = self.right_margin));
+
+        vec![rating]
+    }
+
     fn primary_swell(&self) -> Vec<Line> {
         if self.is_primary_present() {
             self.swell("Primary", |sw: SwellComponents| sw.primary)
@@ -425,8 +653,8 @@ impl<'a> Day<'a> {
 
         // Render the legend // ↜, ↝
         swell[HEIGHT_IX].push(span!("{:^width$}", "", width = Self::LEGEND_WIDTH));
-        swell[PERIOD_IX].push(span!("{:^width$}", legend, width = Self::LEGEND_WIDTH));
-        swell[DIR_IX].push(span!("{:^width$}", "Swell", width = Self::LEGEND_WIDTH));
+        swell[PERIOD_IX].push(label(&legend.to_string(), Self::LEGEND_WIDTH));
+        swell[DIR_IX].push(label("Swell", Self::LEGEND_WIDTH));
 
         // Render each timestamp forecast
         for fc in self.forecast {
@@ -446,11 +674,7 @@ impl<'a> Day<'a> {
                     span!("{:^bin_width$}", str)
                 });
                 swell[DIR_IX].push({
-                    let str = format!(
-                        "{arrow} {deg:.0}°",
-                        arrow = compass_to_arrow(c.compass_direction),
-                        deg = c.direction
-                    );
+                    let str = bearing(c.compass_direction, c.direction, self.context.charset);
                     span!("{:^bin_width$}", str)
                 });
             } else {
@@ -467,12 +691,14 @@ impl<'a> Day<'a> {
     fn wind(&self) -> Vec<Line> {
         const SPEED_IX: usize = 0;
         const DIR_IX: usize = 1;
+        const SHORE_IX: usize = 2;
         let init = Vec::with_capacity(2 * self.forecast.len() + 2);
-        let mut wind = [init.clone(), init];
+        let mut wind = [init.clone(), init.clone(), init];
 
         // Render the legend // use 🌫
         wind[SPEED_IX].push(span!("{:^width$}", " ", width = Self::LEGEND_WIDTH));
-        wind[DIR_IX].push(span!("{:^width$}", " Wind", width = Self::LEGEND_WIDTH));
+        wind[DIR_IX].push(label(" Wind", Self::LEGEND_WIDTH));
+        wind[SHORE_IX].push(span!("{:^width$}", "", width = Self::LEGEND_WIDTH));
 
         // Render each timestamp forecast
         for fc in self.forecast {
@@ -482,18 +708,32 @@ impl<'a> Day<'a> {
                 span!("{:^width$}", str, width = self.bin_width)
             });
             wind[DIR_IX].push({
-                let str = format!(
-                    "{arrow} {deg:.0}°",
-                    arrow = compass_to_arrow(fc.wind.compass_direction),
-                    deg = fc.wind.direction
+                let str = bearing(
+                    fc.wind.compass_direction,
+                    fc.wind.direction,
+                    self.context.charset,
                 );
                 span!("{:^width$}", str, width = self.bin_width)
             });
+            wind[SHORE_IX].push({
+                let mut span = span!("{:^width$}", "", width = self.bin_width);
+                if let Some(bearing) = self.context.bearing {
+                    let shore = WindShore::classify(bearing, &fc.wind);
+                    let mut str = shore.to_string();
+                    if str.chars().count() > self.bin_width {
+                        str = shore_abbrev(shore).to_owned();
+                    }
+                    span = span!("{:^width$}", str, width = self.bin_width);
+                    span.style().role(wind_role(shore));
+                }
+                span
+            });
         }
         wind.iter_mut()
             .for_each(|row| row.push(span!("{:width$}", "", width = self.right_margin)));
 
-        wind.to_vec()
+        let rows = if self.context.bearing.is_some() { 3 } else { 2 };
+        wind[..rows].to_vec()
     }
 
     fn weather(&self) -> Vec<Line> {
@@ -501,16 +741,16 @@ impl<'a> Day<'a> {
         let mut weather = Vec::with_capacity(2 * self.forecast.len() + 2);
 
         // Render the legend // unicode icons: ☼ 🌣 🌤 🌧 🌩
-        weather.push(span!("{:^width$}", "Air", width = Self::LEGEND_WIDTH));
+        weather.push(label("Air", Self::LEGEND_WIDTH));
 
         // Render each timestamp forecast
         for fc in self.forecast {
             weather.push(self.boundary());
             weather.push({
-                let str = format!(
-                    "{temp} {unit}",
-                    temp = fc.condition.temperature,
-                    unit = fc.condition.unit_temperature
+                let str = temperature(
+                    fc.condition.temperature,
+                    fc.condition.unit_temperature,
+                    self.context.charset,
                 );
                 span!("{:^width$}", str, width = self.bin_width)
             });
@@ -520,6 +760,249 @@ impl<'a> Day<'a> {
         vec![weather]
     }
 
+    fn daylight(&self) -> Vec<Line> {
+        let times = self.daylight_times();
+        self.wrapped("Daylight", times)
+    }
+
+    /// First light, sunrise, sunset, and last light, if the location is known
+    fn daylight_times(&self) -> Vec<String> {
+        let first = self.forecast.first().unwrap();
+        let times = match self
+            .context
+            .daylight
+            .and_then(|daylight| daylight.get(first.local_timestamp.date()))
+        {
+            Some(times) => times,
+            None => return vec![],
+        };
+        let utc_offset = first.local_timestamp.timestamp() - first.timestamp;
+        [
+            ("First light", times.civil_dawn),
+            ("Sunrise", times.sunrise),
+            ("Sunset", times.sunset),
+            ("Last light", times.civil_dusk),
+        ]
+        .iter()
+        .map(|(label, timestamp)| {
+            let time = timestamp
+                .and_then(|t| NaiveDateTime::from_timestamp_opt(t + utc_offset, 0))
+                .map_or_else(
+                    || "-".to_owned(),
+                    |local| local.format("%l:%M%P").to_string().trim().to_owned(),
+                );
+            format!("{label} {time}")
+        })
+        .collect()
+    }
+
+    /// Lines of text beside a legend, wrapped to fit
+    fn wrapped(&self, legend: &str, parts: Vec<String>) -> Vec<Line> {
+        let width = self.interior_width() - Self::LEGEND_WIDTH;
+        wrap(parts, "   ", width)
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let legend = if i == 0 { legend } else { "" };
+                vec![
+                    label(legend, Self::LEGEND_WIDTH),
+                    span!("{:^width$}", line, width = width),
+                ]
+            })
+            .collect()
+    }
+
+    fn tide(&self) -> Vec<Line> {
+        let station = match self.context.tides {
+            Some(station) => station,
+            None => return vec![],
+        };
+        let mut heights = Vec::with_capacity(2 * self.forecast.len() + 2);
+        heights.push(label("Tide", Self::LEGEND_WIDTH));
+        for fc in self.forecast {
+            heights.push(self.boundary());
+            heights.push({
+                let trend = trend(station.is_rising(fc.timestamp), self.context.charset);
+                let height = tenths(station.height(fc.timestamp));
+                let unit = station.unit;
+                // Squeeze out spaces to fit narrow columns
+                let str = [
+                    format!("{height:.1} {unit} {trend}"),
+                    format!("{height:.1}{unit} {trend}"),
+                ]
+                .into_iter()
+                .find(|str| str.chars().count() <= self.bin_width)
+                .unwrap_or_else(|| format!("{height:.1}{unit}{trend}"));
+                span!("{:^width$}", str, width = self.bin_width)
+            });
+        }
+        heights.push(span!("{:width$}", "", width = self.right_margin));
+
+        let mut lines = vec![heights];
+        lines.extend(self.wrapped("", self.tide_extremes()));
+        lines
+    }
+
+    /// High and low tides throughout the (local) day, if the station is known
+    fn tide_extremes(&self) -> Vec<String> {
+        let station = match self.context.tides {
+            Some(station) => station,
+            None => return vec![],
+        };
+        let first = self.forecast.first().unwrap();
+        let utc_offset = first.local_timestamp.timestamp() - first.timestamp;
+        let start = first.timestamp - first.local_timestamp.num_seconds_from_midnight() as i64;
+        station
+            .extremes(start, start + 24 * 60 * 60)
+            .into_iter()
+            .filter_map(|extreme| {
+                let local = NaiveDateTime::from_timestamp_opt(extreme.timestamp + utc_offset, 0)?;
+                let kind = match extreme.kind {
+                    TideKind::High => "Hi",
+                    TideKind::Low => "Lo",
+                };
+                Some(format!(
+                    "{kind} {} {:.1}{}",
+                    local.format("%l:%M%P").to_string().trim(),
+                    tenths(extreme.height),
+                    station.unit
+                ))
+            })
+            .collect()
+    }
+
+    /// One row per interval, with a column for each value that fits, in order
+    /// of importance. Daylight and tide times follow beneath.
+    fn vertical(&self) -> Vec<Line> {
+        let interior_width = self.interior_width();
+        let columns = self.columns(interior_width);
+        let used_space = columns.iter().map(|column| column.width).sum::<usize>()
+            + columns.len().saturating_sub(1) * Self::BOUNDARY_WIDTH;
+        let margin = interior_width.saturating_sub(used_space);
+        let (left_margin, right_margin) = (margin / 2, margin - margin / 2);
+        let row = |cells: Vec<Span>| {
+            let mut line = Vec::with_capacity(2 * cells.len() + 2);
+            line.push(span!("{:width$}", "", width = left_margin));
+            for (i, (column, mut span)) in columns.iter().zip(cells).enumerate() {
+                if i > 0 {
+                    line.push(self.boundary());
+                }
+                if let Content::Text(text) = &span.content {
+                    span.content = Content::Text(format!("{:^width$}", text, width = column.width));
+                }
+                line.push(span);
+            }
+            line.push(span!("{:width$}", "", width = right_margin));
+            line
+        };
+
+        let mut lines = vec![row(columns
+            .iter()
+            .map(|column| label(column.header, 0))
+            .collect())];
+        for fc in self.forecast {
+            lines.push(row(columns
+                .iter()
+                .map(|column| (column.cell)(fc))
+                .collect()));
+        }
+        for parts in [self.daylight_times(), self.tide_extremes()] {
+            if parts.is_empty() {
+                continue;
+            }
+            lines.push(vec![span!("{:width$}", "", width = interior_width)]);
+            for line in wrap(parts, "  ", interior_width) {
+                lines.push(vec![span!("{:^width$}", line, width = interior_width)]);
+            }
+        }
+        lines
+    }
+
+    /// Columns for the vertical layout, in display order. Time, surf, swell,
+    /// and wind are always shown; the rest only while they fit, by priority.
+    fn columns(&self, width: usize) -> Vec<Column<'_>> {
+        let charset = self.context.charset;
+        let mut columns = vec![
+            Column::new("Time", 5, 0, |fc| {
+                span!("{}", fc.local_timestamp.format("%l%P").to_string().trim())
+            }),
+            Column::new("Surf", 8, 0, |fc| {
+                let mut span = span!("{}", height_range(fc));
+                span.style().role(self.context.role(fc));
+                span
+            }),
+            Column::new("Swell", 6, 0, move |fc| match fc.swell.components.primary {
+                Some(c) => span!("{}s {}", c.period, compass(c.compass_direction, charset)),
+                None => span!("-"),
+            }),
+            Column::new("Wind", 8, 0, move |fc| {
+                let arrow = compass(fc.wind.compass_direction, charset);
+                span!("{arrow} {}{}", fc.wind.speed, fc.wind.unit)
+            }),
+            Column::new("Air", 5, 4, move |fc| {
+                Span::new(temperature(
+                    fc.condition.temperature,
+                    fc.condition.unit_temperature,
+                    charset,
+                ))
+            }),
+        ];
+        if let Some(rater) = self.context.rater {
+            columns.insert(
+                1,
+                Column::new("Rating", 6, 1, move |fc| {
+                    let stars = rater.rate(fc).stars;
+                    let mut span = span!("{}", stars_str(stars));
+                    span.style().role(stars_role(stars));
+                    span
+                }),
+            );
+        }
+        if let Some(bearing) = self.context.bearing {
+            let ix = columns.len() - 1;
+            columns.insert(
+                ix,
+                Column::new("Shore", 9, 3, move |fc| {
+                    let shore = WindShore::classify(bearing, &fc.wind);
+                    let mut span = span!("{shore}");
+                    span.style().role(wind_role(shore));
+                    span
+                }),
+            );
+        }
+        if let Some(station) = self.context.tides {
+            let ix = columns.len() - 1;
+            columns.insert(
+                ix,
+                Column::new("Tide", 8, 2, move |fc| {
+                    let trend = trend(station.is_rising(fc.timestamp), charset);
+                    let height = tenths(station.height(fc.timestamp));
+                    span!("{height:.1}{} {trend}", station.unit)
+                }),
+            );
+        }
+
+        // Fill the width by priority, keeping display order
+        let mut used = 0;
+        let mut keep = vec![false; columns.len()];
+        for (ix, column) in columns
+            .iter()
+            .enumerate()
+            .sorted_by_key(|(_, column)| column.priority)
+        {
+            let needed = column.width + if used > 0 { Self::BOUNDARY_WIDTH } else { 0 };
+            if column.priority == 0 || used + needed <= width {
+                used += needed;
+                keep[ix] = true;
+            }
+        }
+        columns
+            .into_iter()
+            .zip(keep)
+            .filter_map(|(column, keep)| keep.then_some(column))
+            .collect()
+    }
+
     /// The span between columns
     fn boundary(&self) -> Span {
         //span!("{LINE_VERT}")
@@ -543,6 +1026,124 @@ impl<'a> Day<'a> {
     }
 }
 
+/// A column of the vertical day layout
+struct Column<'a> {
+    header: &'static str,
+    width: usize,
+    /// Lower is more important, and zero is always shown
+    priority: u8,
+    cell: Box<dyn Fn(&Forecast) -> Span + 'a>,
+}
+
+impl<'a> Column<'a> {
+    fn new<F>(header: &'static str, width: usize, priority: u8, cell: F) -> Self
+    where
+        F: Fn(&Forecast) -> Span + 'a,
+    {
+        Self {
+            header,
+            width,
+            priority,
+            cell: Box::new(cell),
+        }
+    }
+}
+
+/// Whether a star rating is good, fair, or poor
+pub(super) fn stars_role(stars: u8) -> Role {
+    Role::Rating(Level::of(stars))
+}
+
+const STAR_SOLID: &str = "★";
+const STAR_EMPTY: &str = "☆";
+
+/// Solid stars for the score, then empty ones up to [`MAX_STARS`]
+pub(super) fn stars_str(stars: u8) -> String {
+    format!(
+        "{}{}",
+        STAR_SOLID.repeat(stars as usize),
+        STAR_EMPTY.repeat((MAX_STARS - stars) as usize)
+    )
+}
+
+/// Favorable wind is good, marginal is fair, and the rest is poor
+fn wind_role(shore: WindShore) -> Role {
+    match shore {
+        WindShore::Offshore | WindShore::CrossOffshore => Role::Rating(Level::Good),
+        WindShore::CrossShore => Role::Rating(Level::Fair),
+        WindShore::CrossOnshore | WindShore::Onshore => Role::Rating(Level::Poor),
+    }
+}
+
+/// Wind relative to the shore, for narrow columns
+fn shore_abbrev(shore: WindShore) -> &'static str {
+    match shore {
+        WindShore::Offshore => "off",
+        WindShore::CrossOffshore => "x-off",
+        WindShore::CrossShore => "cross",
+        WindShore::CrossOnshore => "x-on",
+        WindShore::Onshore => "on",
+    }
+}
+
+/// Marks the tide level on the swell graph
+const TIDE_MARKER: &str = "~";
+
+/// Round to one decimal place, avoiding a displayed "-0.0"
+pub(super) fn tenths(x: f32) -> f32 {
+    (x * 10.0).round() / 10.0 + 0.0
+}
+
+/// Breaking height range, e.g. `2-3ft` or `0.6-0.9m`
+pub(super) fn height_range(fc: &Forecast) -> String {
+    let (min, max, unit) = (
+        fc.swell.min_breaking_height,
+        fc.swell.max_breaking_height,
+        fc.swell.unit,
+    );
+    match unit {
+        UnitLength::Feet => format!("{min:.0}-{max:.0}{unit}"),
+        UnitLength::Meters => format!("{min:.1}-{max:.1}{unit}"),
+    }
+}
+
+/// Which way the wind or swell is heading as an arrow, or in ASCII, the
+/// compass point it comes from
+pub(super) fn compass(dir: CompassDirection, charset: Charset) -> &'static str {
+    match charset {
+        Charset::Unicode => compass_to_arrow(dir),
+        Charset::Ascii => compass_point(dir),
+    }
+}
+
+/// Whether the tide is rising or falling, as an arrow
+fn trend(rising: bool, charset: Charset) -> &'static str {
+    match (charset, rising) {
+        (Charset::Unicode, true) => "↑",
+        (Charset::Unicode, false) => "↓",
+        (Charset::Ascii, true) => "^",
+        (Charset::Ascii, false) => "v",
+    }
+}
+
+/// A direction along with its bearing, e.g. `↙ 197°`
+pub(super) fn bearing(dir: CompassDirection, degrees: f32, charset: Charset) -> String {
+    format!(
+        "{} {degrees:.0}{}",
+        compass(dir, charset),
+        charset.degrees()
+    )
+}
+
+/// A temperature along with its unit, e.g. `57 °F`
+fn temperature(temp: i32, unit: UnitTemperature, charset: Charset) -> String {
+    let unit = match unit {
+        UnitTemperature::C => "C",
+        UnitTemperature::F => "F",
+    };
+    format!("{temp} {}{unit}", charset.degrees())
+}
+
 fn compass_to_arrow(dir: CompassDirection) -> &'static str {
     use CompassDirection::*;
     match dir {
@@ -556,3 +1157,49 @@ fn compass_to_arrow(dir: CompassDirection) -> &'static str {
         WNW | NW | NNW => "↘",
     }
 }
+
+/// The nearest of the eight points the arrows show
+fn compass_point(dir: CompassDirection) -> &'static str {
+    use CompassDirection::*;
+    match dir {
+        N => "N",
+        NNE | NE | ENE => "NE",
+        E => "E",
+        ESE | SE | SSE => "SE",
+        S => "S",
+        SSW | SW | WSW => "SW",
+        W => "W",
+        WNW | NW | NNW => "NW",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spot::Coordinates;
+    use crate::ui::view::layout::{DEFAULT_WIDTH, MAX_WIDTH, MIN_WIDTH};
+    use crate::ui::{render_width, Ascii, Plain};
+
+    #[test]
+    fn report_fits_width() {
+        let json = include_str!("../../../../test/msw/forecast.json");
+        let forecast: Vec<Forecast> = serde_json::from_str(json).unwrap();
+        let spot = Spot::new(4203, "ormond-beach")
+            .bearing(90.0)
+            .tide_station("8721120")
+            .coordinates(Coordinates::new(29.28, -81.05));
+        for width in [MIN_WIDTH, 60, 80, DEFAULT_WIDTH, 120, MAX_WIDTH] {
+            let report = Report::for_spot(forecast.clone(), &spot);
+            let output = render_width(Plain, report, width);
+            for line in output.lines() {
+                assert_eq!(line.chars().count(), width, "{line}");
+            }
+            let report = Report::for_spot(forecast.clone(), &spot);
+            let output = render_width(Ascii, report, width);
+            assert!(output.is_ascii() && !output.contains('?'), "{output}");
+            for line in output.lines() {
+                assert_eq!(line.len(), width, "{line}");
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Fitting views to the width of the viewpoint, from phones to wide terminals
+
+use super::base::View;
+
+/// Total width of the output when none is requested. Wide enough for every
+/// view's full layout, and narrow enough for most terminals.
+pub const DEFAULT_WIDTH: usize = 90;
+/// Narrowest supported width, about that of a phone held upright
+pub const MIN_WIDTH: usize = 40;
+/// Widest supported width. Views just spread out beyond the default.
+pub const MAX_WIDTH: usize = 200;
+
+/// Characters a view may be drawn with
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Charset {
+    /// Box drawing, arrows, stars and the like
+    #[default]
+    Unicode,
+    /// 7-bit ASCII. Views spell out directions where they'd otherwise draw
+    /// arrows.
+    Ascii,
+}
+
+impl Charset {
+    /// The degree sign, which ASCII lacks
+    pub(super) fn degrees(self) -> &'static str {
+        match self {
+            Charset::Unicode => "°",
+            Charset::Ascii => "",
+        }
+    }
+}
+
+/// Something that can be laid out to fit a given width
+pub trait Layout {
+    /// Lay out within `width` columns, assumed to be within [`MIN_WIDTH`] and
+    /// [`MAX_WIDTH`]
+    fn layout(self, width: usize, charset: Charset) -> View;
+}
+
+impl<T: Layout> From<T> for View {
+    fn from(view: T) -> Self {
+        view.layout(DEFAULT_WIDTH, Charset::default())
+    }
+}
+
+/// Bring a requested width within the supported range
+pub fn clamp(width: usize) -> usize {
+    width.clamp(MIN_WIDTH, MAX_WIDTH)
+}
+
+/// How many columns of at least `min_bin_width` fit alongside a legend, with a
+/// one char boundary before each column
+pub(super) fn max_columns(
+    interior_width: usize,
+    legend_width: usize,
+    min_bin_width: usize,
+) -> usize {
+    (interior_width.saturating_sub(legend_width) / (min_bin_width + 1)).max(1)
+}
+
+/// Cut a string down to at most `width` characters
+pub(super) fn truncate(s: &str, width: usize) -> String {
+    s.chars().take(width).collect()
+}
+
+/// Pack the parts into as few lines as fit within `width`, separated by `sep`.
+/// A part wider than `width` gets a line to itself.
+pub(super) fn wrap<I>(parts: I, sep: &str, width: usize) -> Vec<String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut lines: Vec<String> = Vec::new();
+    for part in parts {
+        match lines.last_mut() {
+            Some(line)
+                if line.chars().count() + sep.chars().count() + part.chars().count() <= width =>
+            {
+                line.push_str(sep);
+                line.push_str(&part);
+            }
+            _ => lines.push(part),
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_parts_to_width() {
+        let parts = || ["Hi 4:53am", "Lo 11:29am", "Hi 5:21pm"].map(String::from);
+        assert_eq!(
+            wrap(parts(), "   ", 40),
+            vec!["Hi 4:53am   Lo 11:29am   Hi 5:21pm"]
+        );
+        assert_eq!(
+            wrap(parts(), "   ", 25),
+            vec!["Hi 4:53am   Lo 11:29am", "Hi 5:21pm"]
+        );
+        assert_eq!(wrap(parts(), "   ", 5).len(), 3);
+        assert!(wrap(Vec::new(), " ", 10).is_empty());
+    }
+
+    #[test]
+    fn fits_columns() {
+        assert_eq!(max_columns(88, 11, 8), 8);
+        assert_eq!(max_columns(38, 11, 8), 3);
+        assert_eq!(max_columns(10, 11, 8), 1);
+        assert_eq!(clamp(10), MIN_WIDTH);
+        assert_eq!(clamp(1000), MAX_WIDTH);
+    }
+}
@@ -1,6 +1,17 @@
 mod base;
+mod border;
+pub mod buoy;
+pub mod chart;
+mod columns;
+pub mod compare;
+pub mod diff;
+pub mod favorites;
 pub mod forecast;
+pub mod layout;
 pub mod rip;
 pub mod spots;
+pub mod table;
+pub mod tide;
 
-pub use base::{Color, Content, Span, Style, View};
+pub use base::{Color, Content, Role, Span, Style, View};
+pub use layout::Layout;
@@ -1,4 +1,5 @@
 use super::base::{Span, View};
+use super::layout::{Charset, Layout};
 
 const RIP: &str = r#"
   ██████╗ ██╗██████╗     ███╗   ███╗███████╗██╗    ██╗
@@ -9,12 +10,24 @@ const RIP: &str = r#"
   ╚═╝  ╚═╝╚═╝╚═╝         ╚═╝     ╚═╝╚══════╝ ╚══╝╚══╝
 "#;
 
+const RIP_ASCII: &str = r#"
+  ____  ___ ____    __  __ ______        __
+ |  _ \|_ _|  _ \  |  \/  / ___\ \      / /
+ | |_) || || |_) | | |\/| \___ \\ \ /\ / /
+ |  _ < | ||  __/  | |  | |___) |\ V  V /
+ |_| \_\___|_|     |_|  |_|____/  \_/\_/
+"#;
+
 pub struct Rip;
 
-impl From<Rip> for View {
-    fn from(_: Rip) -> Self {
-        Self {
-            spans: vec![Span::new(RIP)],
+impl Layout for Rip {
+    fn layout(self, _width: usize, charset: Charset) -> View {
+        let art = match charset {
+            Charset::Unicode => RIP,
+            Charset::Ascii => RIP_ASCII,
+        };
+        View {
+            spans: vec![Span::new(art)],
         }
     }
 }
@@ -0,0 +1,16 @@
+//! Tables that renderers able to lay out tables show in place of their text
+
+use super::base::*;
+
+/// Rows of cells under a title, e.g. a day of the forecast
+#[derive(Clone, Debug, PartialEq)]
+pub struct Table {
+    pub title: String,
+    pub header: Vec<String>,
+    /// One span per cell, styled like the text
+    pub rows: Vec<Vec<Span>>,
+    /// Lines beneath the rows, e.g. daylight and tide times
+    pub notes: Vec<String>,
+    /// The same table drawn as text, for renderers that only draw text
+    pub text: Vec<Span>,
+}
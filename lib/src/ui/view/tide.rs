@@ -0,0 +1,178 @@
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+
+use super::base::*;
+use super::border::Border;
+use super::forecast::tenths;
+use super::layout::{wrap, Charset, Layout, DEFAULT_WIDTH};
+use crate::tide::{Station, TideKind};
+
+/// High and low tides for a number of days, one line per day
+pub struct Tides {
+    station: Station,
+    start: i64,
+    days: u32,
+    /// Time zone to show times in, or this machine's when unknown
+    zone: Option<Tz>,
+    width: usize,
+}
+
+impl Tides {
+    /// Tides for the local days starting with the one containing the `start`
+    /// unix timestamp, in this machine's time zone
+    pub fn new(station: Station, start: i64, days: u32) -> Self {
+        Self {
+            station,
+            start,
+            days,
+            zone: None,
+            width: DEFAULT_WIDTH,
+        }
+    }
+
+    /// Show days and times in the given zone, e.g. the spot's
+    pub fn zone(mut self, zone: Tz) -> Self {
+        self.zone = Some(zone);
+        self
+    }
+
+    fn local(&self, timestamp: i64) -> NaiveDateTime {
+        match self.zone {
+            Some(zone) => local(&zone, timestamp),
+            None => local(&Local, timestamp),
+        }
+    }
+
+    fn midnight(&self, date: NaiveDate) -> i64 {
+        match self.zone {
+            Some(zone) => midnight(&zone, date),
+            None => midnight(&Local, date),
+        }
+    }
+}
+
+/// Wall clock time of a unix timestamp, with the zone's offset at that time
+fn local<Z: TimeZone>(zone: &Z, timestamp: i64) -> NaiveDateTime {
+    zone.timestamp(timestamp, 0).naive_local()
+}
+
+/// Unix timestamp of the start of the date, which is 1am where a DST change
+/// skips midnight
+fn midnight<Z: TimeZone>(zone: &Z, date: NaiveDate) -> i64 {
+    [date.and_hms(0, 0, 0), date.and_hms(1, 0, 0)]
+        .iter()
+        .find_map(|time| zone.from_local_datetime(time).earliest())
+        .map_or_else(
+            || date.and_hms(0, 0, 0).timestamp(),
+            |time| time.timestamp(),
+        )
+}
+
+impl Layout for Tides {
+    fn layout(self, width: usize, _charset: Charset) -> View {
+        View {
+            spans: Self { width, ..self }.draw(),
+        }
+    }
+}
+
+impl Border for Tides {
+    fn title(&self) -> String {
+        let zone = self.zone.map_or("local time", |zone| zone.name());
+        format!("Tides - {} ({zone})", self.station.name)
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    // Days that don't fit on one line wrap, aligned with the first tide
+    fn draw_inner(&self) -> Vec<Line> {
+        const DATE_WIDTH: usize = 13;
+        let extremes_width = self.interior_width() - DATE_WIDTH;
+        let first_day = self.local(self.start).date();
+        let mut lines = Vec::new();
+        for day in 0..self.days {
+            let date = first_day + Duration::days(day as i64);
+            let (start, end) = (self.midnight(date), self.midnight(date.succ()));
+            let extremes = self
+                .station
+                .extremes(start, end)
+                .into_iter()
+                .map(|extreme| {
+                    let local = self.local(extreme.timestamp);
+                    let kind = match extreme.kind {
+                        TideKind::High => "Hi",
+                        TideKind::Low => "Lo",
+                    };
+                    format!(
+                        "{kind} {:>7} {:>4.1}{}",
+                        local.format("%l:%M%P").to_string().trim(),
+                        tenths(extreme.height),
+                        self.station.unit
+                    )
+                });
+            let mut date = span!(
+                " {:<width$}",
+                date.format("%a %b %d"),
+                width = DATE_WIDTH - 1
+            );
+            let mut extremes = wrap(extremes, "  ", extremes_width);
+            if extremes.is_empty() {
+                extremes.push(String::new());
+            }
+            for extremes in extremes {
+                lines.push(vec![
+                    date.clone(),
+                    span!("{:<width$}", extremes, width = extremes_width),
+                ]);
+                date = span!("{:width$}", "", width = DATE_WIDTH);
+            }
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::view::layout::{MAX_WIDTH, MIN_WIDTH};
+    use crate::ui::{render_width, Plain};
+
+    #[test]
+    fn tides_fit_width() {
+        let station = Station::builtin("8721120").unwrap();
+        for width in [MIN_WIDTH, DEFAULT_WIDTH, MAX_WIDTH] {
+            let tides =
+                Tides::new(station.clone(), 1657004400, 3).zone(chrono_tz::America::New_York);
+            let output = render_width(Plain, tides, width);
+            for line in output.lines() {
+                assert_eq!(line.chars().count(), width, "{line}");
+            }
+            for day in ["Tue Jul 05", "Wed Jul 06", "Thu Jul 07"] {
+                assert!(output.contains(day), "{output}");
+            }
+            assert!(output.contains("Hi ") && output.contains("Lo "));
+        }
+    }
+
+    #[test]
+    fn shows_times_in_spot_zone_across_dst() {
+        let station = Station::builtin("8721120").unwrap();
+        // Noon UTC on Sat Mar 12 2022, the day before US clocks went forward
+        let tides = Tides::new(station.clone(), 1647086400, 3).zone(chrono_tz::America::New_York);
+        let output = render_width(Plain, tides, DEFAULT_WIDTH);
+        assert!(output.contains("(America/New_York)"), "{output}");
+        // EST until 2am local on Mar 13, EDT after
+        let dst = 1647154800;
+        let extremes = station.extremes(1647061200, 1647316800);
+        assert!(extremes.iter().any(|e| e.timestamp < dst));
+        assert!(extremes.iter().any(|e| e.timestamp >= dst));
+        for extreme in extremes {
+            let offset = if extreme.timestamp < dst { -5 } else { -4 };
+            let local = NaiveDateTime::from_timestamp(extreme.timestamp + offset * 3600, 0);
+            let time = local.format("%l:%M%P").to_string();
+            assert!(output.contains(time.trim()), "{time} missing from {output}");
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! Conversion between units of measurement, so that forecasts can be shown in
+//! any units regardless of what the provider supports.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+use crate::msw::forecast::{
+    Forecast, SwellComponent, UnitLength, UnitPressure, UnitSpeed, UnitTemperature, UnitType,
+};
+
+/// Units to convert a forecast into. Any left as `None` are unchanged.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Units {
+    pub length: Option<UnitLength>,
+    pub speed: Option<UnitSpeed>,
+    pub temperature: Option<UnitTemperature>,
+    pub pressure: Option<UnitPressure>,
+}
+
+impl From<UnitType> for Units {
+    /// The unit presets MSW supported
+    fn from(unit_type: UnitType) -> Self {
+        let (length, speed, temperature) = match unit_type {
+            UnitType::Us => (UnitLength::Feet, UnitSpeed::Mph, UnitTemperature::F),
+            UnitType::Uk => (UnitLength::Feet, UnitSpeed::Mph, UnitTemperature::C),
+            UnitType::Eu => (UnitLength::Meters, UnitSpeed::Kph, UnitTemperature::C),
+        };
+        Self {
+            length: Some(length),
+            speed: Some(speed),
+            temperature: Some(temperature),
+            pressure: None,
+        }
+    }
+}
+
+impl FromStr for Units {
+    type Err = anyhow::Error;
+
+    /// Parse a comma separated list of presets and units, where later entries
+    /// take precedence, e.g. `eu,kts` or `m,kts,f`
+    fn from_str(s: &str) -> Result<Self> {
+        let mut units = Units::default();
+        for token in s.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            let token = token.to_lowercase();
+            match token.as_str() {
+                "us" => units = units.merge(UnitType::Us.into()),
+                "uk" => units = units.merge(UnitType::Uk.into()),
+                "eu" => units = units.merge(UnitType::Eu.into()),
+                "ft" | "feet" => units.length = Some(UnitLength::Feet),
+                "m" | "meters" => units.length = Some(UnitLength::Meters),
+                "mph" => units.speed = Some(UnitSpeed::Mph),
+                "kph" | "kmh" => units.speed = Some(UnitSpeed::Kph),
+                "kts" | "knots" => units.speed = Some(UnitSpeed::Knots),
+                "mps" | "m/s" => units.speed = Some(UnitSpeed::MetersPerSecond),
+                "c" => units.temperature = Some(UnitTemperature::C),
+                "f" => units.temperature = Some(UnitTemperature::F),
+                "mb" | "hpa" => units.pressure = Some(UnitPressure::Millibars),
+                "inhg" => units.pressure = Some(UnitPressure::InchesOfMercury),
+                _ => return Err(anyhow!("Unknown unit {token:?}")),
+            }
+        }
+        Ok(units)
+    }
+}
+
+impl TryFrom<String> for Units {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl Units {
+    /// Combine with other units, preferring the other's where both are set
+    pub fn merge(self, other: Units) -> Self {
+        Self {
+            length: other.length.or(self.length),
+            speed: other.speed.or(self.speed),
+            temperature: other.temperature.or(self.temperature),
+            pressure: other.pressure.or(self.pressure),
+        }
+    }
+}
+
+impl UnitLength {
+    pub fn convert(self, value: f32, to: UnitLength) -> f32 {
+        match (self, to) {
+            (UnitLength::Feet, UnitLength::Meters) => value * 0.3048,
+            (UnitLength::Meters, UnitLength::Feet) => value / 0.3048,
+            _ => value,
+        }
+    }
+}
+
+impl UnitSpeed {
+    fn kph_per_unit(self) -> f32 {
+        match self {
+            UnitSpeed::Mph => 1.609_344,
+            UnitSpeed::Kph => 1.0,
+            UnitSpeed::Knots => 1.852,
+            UnitSpeed::MetersPerSecond => 3.6,
+        }
+    }
+
+    pub fn convert(self, value: f32, to: UnitSpeed) -> f32 {
+        value * self.kph_per_unit() / to.kph_per_unit()
+    }
+}
+
+impl UnitTemperature {
+    pub fn convert(self, value: f32, to: UnitTemperature) -> f32 {
+        match (self, to) {
+            (UnitTemperature::C, UnitTemperature::F) => value * 9.0 / 5.0 + 32.0,
+            (UnitTemperature::F, UnitTemperature::C) => (value - 32.0) * 5.0 / 9.0,
+            _ => value,
+        }
+    }
+}
+
+impl UnitPressure {
+    pub fn convert(self, value: f32, to: UnitPressure) -> f32 {
+        const MB_PER_INHG: f32 = 33.863_89;
+        match (self, to) {
+            (UnitPressure::Millibars, UnitPressure::InchesOfMercury) => value / MB_PER_INHG,
+            (UnitPressure::InchesOfMercury, UnitPressure::Millibars) => value * MB_PER_INHG,
+            _ => value,
+        }
+    }
+}
+
+impl Forecast {
+    /// Convert all values into the given units. Converted values are rounded
+    /// to a sensible precision for display.
+    pub fn in_units(mut self, units: &Units) -> Self {
+        if let Some(to) = units.length.filter(|to| *to != self.swell.unit) {
+            let from = self.swell.unit;
+            let length = |x: f32| round_to(from.convert(x, to), 1);
+            let swell = &mut self.swell;
+            swell.min_breaking_height = length(swell.min_breaking_height);
+            swell.max_breaking_height = length(swell.max_breaking_height);
+            swell.abs_min_breaking_height =
+                round_to(from.convert(swell.abs_min_breaking_height, to), 2);
+            swell.abs_max_breaking_height =
+                round_to(from.convert(swell.abs_max_breaking_height, to), 2);
+            let components = &mut swell.components;
+            for component in [
+                &mut components.combined,
+                &mut components.primary,
+                &mut components.secondary,
+                &mut components.tertiary,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                let SwellComponent { height, .. } = component;
+                *height = length(*height);
+            }
+            swell.unit = to;
+        }
+
+        if let Some(to) = units.speed.filter(|to| *to != self.wind.unit) {
+            let from = self.wind.unit;
+            let speed = |x: u32| from.convert(x as f32, to).round() as u32;
+            self.wind.speed = speed(self.wind.speed);
+            self.wind.gusts = speed(self.wind.gusts);
+            self.wind.unit = to;
+        }
+
+        if let Some(to) = units
+            .temperature
+            .filter(|to| *to != self.condition.unit_temperature)
+        {
+            let from = self.condition.unit_temperature;
+            let temperature = |x: i32| from.convert(x as f32, to).round() as i32;
+            self.condition.temperature = temperature(self.condition.temperature);
+            self.wind.chill = temperature(self.wind.chill);
+            self.condition.unit_temperature = to;
+        }
+
+        if let Some(to) = units
+            .pressure
+            .filter(|to| *to != self.condition.unit_pressure)
+        {
+            let precision = match to {
+                UnitPressure::Millibars => 0,
+                UnitPressure::InchesOfMercury => 2,
+            };
+            let from = self.condition.unit_pressure;
+            self.condition.pressure =
+                round_to(from.convert(self.condition.pressure, to), precision);
+            self.condition.unit_pressure = to;
+        }
+
+        self
+    }
+}
+
+fn round_to(value: f32, decimals: i32) -> f32 {
+    let factor = 10f32.powi(decimals);
+    (value * factor).round() / factor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn forecast() -> Forecast {
+        let json = include_str!("../../test/msw/forecast.json");
+        let mut forecast: Vec<Forecast> = serde_json::from_str(json).unwrap();
+        forecast.remove(0)
+    }
+
+    #[test]
+    fn parses_presets_and_mixtures() {
+        assert_eq!("eu".parse::<Units>().unwrap(), UnitType::Eu.into());
+        let mixed: Units = "m,kts".parse().unwrap();
+        assert_eq!(mixed.length, Some(UnitLength::Meters));
+        assert_eq!(mixed.speed, Some(UnitSpeed::Knots));
+        assert_eq!(mixed.temperature, None);
+        let overridden: Units = "us, m/s, inHg".parse().unwrap();
+        assert_eq!(overridden.length, Some(UnitLength::Feet));
+        assert_eq!(overridden.speed, Some(UnitSpeed::MetersPerSecond));
+        assert_eq!(overridden.pressure, Some(UnitPressure::InchesOfMercury));
+        assert!("furlongs".parse::<Units>().is_err());
+    }
+
+    #[test]
+    fn converts_forecast() {
+        let fc = forecast();
+        assert_eq!(fc.swell.unit, UnitLength::Feet);
+        assert_eq!(fc.wind.unit, UnitSpeed::Mph);
+        let units = Units {
+            length: Some(UnitLength::Meters),
+            speed: Some(UnitSpeed::Knots),
+            temperature: Some(UnitTemperature::C),
+            pressure: Some(UnitPressure::InchesOfMercury),
+        };
+        let converted = fc.clone().in_units(&units);
+        assert_eq!(converted.swell.unit, UnitLength::Meters);
+        assert_eq!(
+            converted.swell.max_breaking_height,
+            round_to(fc.swell.max_breaking_height * 0.3048, 1)
+        );
+        assert_eq!(converted.wind.unit, UnitSpeed::Knots);
+        assert_eq!(
+            converted.wind.speed,
+            (fc.wind.speed as f32 * 0.868_976).round() as u32
+        );
+        assert_eq!(converted.condition.unit_temperature, UnitTemperature::C);
+        assert_eq!(
+            converted.condition.unit_pressure,
+            UnitPressure::InchesOfMercury
+        );
+        // 1015 mb
+        assert_eq!(converted.condition.pressure, 29.97);
+    }
+
+    #[test]
+    fn unset_units_are_unchanged() {
+        let fc = forecast();
+        assert_eq!(fc.clone().in_units(&Units::default()), fc);
+        let same = Units::from(UnitType::Us);
+        assert_eq!(fc.clone().in_units(&same), fc);
+    }
+
+    #[test]
+    fn round_trips_speeds() {
+        for unit in [UnitSpeed::Mph, UnitSpeed::Knots, UnitSpeed::MetersPerSecond] {
+            let there = UnitSpeed::Kph.convert(20.0, unit);
+            assert!((unit.convert(there, UnitSpeed::Kph) - 20.0).abs() < 1e-4);
+        }
+    }
+}
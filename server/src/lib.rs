@@ -0,0 +1,764 @@
+//! The thesurf.in web server
+
+use std::{collections::HashMap, future, path::PathBuf};
+
+use actix_web::{
+    error::{ErrorBadRequest, ErrorInternalServerError, ErrorNotFound},
+    get,
+    http::{
+        header::{from_one_raw_str, ACCEPT, USER_AGENT},
+        StatusCode,
+    },
+    web, App, HttpResponse, HttpServer, Responder, Result,
+};
+use futures::future::join_all;
+use lib::api;
+use lib::archive::{Archive, Run};
+use lib::cache::ForecastCache;
+use lib::config::Config;
+use lib::diff::RunDiff;
+use lib::msw::forecast::Forecast;
+use lib::ndbc::{self, BuoyAPI};
+use lib::provider;
+use lib::rating::Rater;
+use lib::spot::{Spot, Spots};
+use lib::ui::{self, Render};
+use serde::{Deserialize, Serialize};
+
+const TERMINAL_USER_AGENTS: [&str; 12] = [
+    "aiohttp",
+    "curl",
+    "fetch",
+    "http_get",
+    "httpie",
+    "lwp-request",
+    "openbsd ftp",
+    "powershell",
+    "python-httpx",
+    "python-requests",
+    "wget",
+    "xh",
+];
+
+/// Server options, defaulting to serving data from `./data` on localhost
+pub struct Server {
+    host: String,
+    port: u16,
+    spots_path: PathBuf,
+    archive_path: PathBuf,
+    provider: String,
+    config: Config,
+}
+
+impl Server {
+    pub fn new() -> Self {
+        Self {
+            host: "127.0.0.1".to_owned(),
+            port: 8080,
+            spots_path: PathBuf::from("./data/spots.json"),
+            archive_path: PathBuf::from("./data/archive"),
+            provider: provider::DEFAULT.to_owned(),
+            config: Config::default(),
+        }
+    }
+
+    pub fn host(mut self, host: impl Into<String>) -> Self {
+        self.host = host.into();
+        self
+    }
+
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Spots file, as written by the crawler
+    pub fn spots_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.spots_path = path.into();
+        self
+    }
+
+    /// Directory to archive fetched forecasts in
+    pub fn archive_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.archive_path = path.into();
+        self
+    }
+
+    /// Name of the upstream forecast provider, see [`provider::PROVIDERS`]
+    pub fn provider(mut self, name: impl Into<String>) -> Self {
+        self.provider = name.into();
+        self
+    }
+
+    /// Honor the user's preferences: spots file, provider, default units,
+    /// favorite aliases, and color. Options set afterwards take precedence.
+    pub fn config(mut self, config: Config) -> Self {
+        if let Some(path) = &config.spots_path {
+            self.spots_path = path.clone();
+        }
+        if let Some(provider) = &config.provider {
+            self.provider = provider.clone();
+        }
+        self.config = config;
+        self
+    }
+
+    /// Run the server on a new actix runtime, blocking until it stops
+    pub fn run(self) -> anyhow::Result<()> {
+        actix_web::rt::System::new().block_on(self.serve())
+    }
+
+    /// Run the server on the current actix runtime
+    pub async fn serve(self) -> anyhow::Result<()> {
+        let spot_data = web::Data::new(Spots::from_path(&self.spots_path)?);
+        let provider = provider::from_name(&self.provider)?;
+        let archive = Archive::open(&self.archive_path)?;
+        let cache_data = web::Data::new(ForecastCache::new(provider).archive(archive.clone()));
+        let archive_data = web::Data::new(archive);
+        let config_data = web::Data::new(self.config);
+        HttpServer::new(move || {
+            App::new()
+                .configure(routes)
+                .app_data(spot_data.clone())
+                .app_data(cache_data.clone())
+                .app_data(archive_data.clone())
+                .app_data(config_data.clone())
+        })
+        .bind((self.host.as_str(), self.port))?
+        .run()
+        .await?;
+        Ok(())
+    }
+}
+
+impl Default for Server {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Register every endpoint. Images come before `/{spot_id}`, which would
+/// otherwise match them.
+fn routes(config: &mut web::ServiceConfig) {
+    config
+        .service(index)
+        .service(ping)
+        .service(metrics)
+        .service(demo)
+        .service(list_spots)
+        .service(get_buoy)
+        .service(get_history)
+        .service(get_diff)
+        .service(compare)
+        .service(get_spot_image)
+        .service(get_spot);
+}
+
+/// Units option wrapper. Exists for actix query params parsing.
+#[derive(Copy, Clone, Debug, Deserialize)]
+struct Units {
+    units: Option<lib::units::Units>,
+}
+
+impl Units {
+    /// Fall back to the configured units when none are requested
+    fn or(self, config: &Config) -> Self {
+        Self {
+            units: self.units.or(config.units),
+        }
+    }
+
+    fn apply(self, forecast: Vec<Forecast>) -> Vec<Forecast> {
+        match self.units {
+            Some(units) => forecast.into_iter().map(|fc| fc.in_units(&units)).collect(),
+            None => forecast,
+        }
+    }
+}
+
+/// Spot forecast shown on the front page
+const INDEX_SPOT: &str = "pipeline";
+
+#[get("/")]
+async fn index(
+    units: web::Query<Units>,
+    spots: web::Data<Spots>,
+    cache: web::Data<ForecastCache>,
+    config: web::Data<Config>,
+    render: RenderChoice,
+) -> Result<HttpResponse> {
+    get_spot_inner(INDEX_SPOT, units, spots, cache, config, render).await
+}
+
+#[get("/ping")]
+async fn ping() -> impl Responder {
+    HttpResponse::Ok().body("pong")
+}
+
+/// Cache counters, in the Prometheus text format
+#[get("/metrics")]
+async fn metrics(cache: web::Data<ForecastCache>) -> impl Responder {
+    let stats = cache.stats();
+    let body = [
+        ("hits", stats.hits),
+        ("stale_hits", stats.stale_hits),
+        ("misses", stats.misses),
+        ("coalesced", stats.coalesced),
+        ("errors", stats.errors),
+        ("archive_errors", stats.archive_errors),
+    ]
+    .iter()
+    .map(|(name, count)| format!("forecast_cache_{name}_total {count}\n"))
+    .collect::<String>();
+    HttpResponse::Ok().content_type("text/plain").body(body)
+}
+
+#[get("/demo")]
+async fn demo(
+    units: web::Query<Units>,
+    config: web::Data<Config>,
+    render: RenderChoice,
+) -> impl Responder {
+    let json = include_str!("../../test/msw/forecast.json");
+    let forecast: Vec<Forecast> = serde_json::from_str(json).unwrap();
+    let forecast = units.or(&config).apply(forecast);
+    match render.format {
+        Format::Json => json_response(api::forecast(None, forecast, None)),
+        _ => render.into_response(forecast).await,
+    }
+}
+
+#[get("/{spot_id}")]
+async fn get_spot(
+    spot_name: web::Path<String>,
+    units: web::Query<Units>,
+    spots: web::Data<Spots>,
+    cache: web::Data<ForecastCache>,
+    config: web::Data<Config>,
+    render: RenderChoice,
+) -> Result<HttpResponse> {
+    get_spot_inner(&spot_name, units, spots, cache, config, render).await
+}
+
+/// The forecast as an image, i.e. `/pipeline.svg` or `/pipeline.png`, to
+/// embed elsewhere
+#[get("/{spot_id}.{extension:svg|png}")]
+async fn get_spot_image(
+    path: web::Path<(String, String)>,
+    units: web::Query<Units>,
+    spots: web::Data<Spots>,
+    cache: web::Data<ForecastCache>,
+    config: web::Data<Config>,
+    mut render: RenderChoice,
+) -> Result<HttpResponse> {
+    let (spot_name, extension) = path.into_inner();
+    render.format = match extension.as_str() {
+        "png" => Format::Png,
+        _ => Format::Svg,
+    };
+    get_spot_inner(&spot_name, units, spots, cache, config, render).await
+}
+
+async fn get_spot_inner(
+    spot_name: &str,
+    units: web::Query<Units>,
+    spots: web::Data<Spots>,
+    cache: web::Data<ForecastCache>,
+    config: web::Data<Config>,
+    render: RenderChoice,
+) -> Result<HttpResponse> {
+    let spot = find_spot(&spots, &config, spot_name)?;
+    let forecast = cache
+        .forecast(&spot, units.or(&config).units)
+        .await
+        .map_err(|e| ErrorInternalServerError(e.to_string()))?;
+    Ok(spot_response(spot, forecast, render).await)
+}
+
+/// Query params for the history endpoint
+#[derive(Clone, Debug, Deserialize)]
+struct History {
+    /// Show the forecast as of this date (UTC), e.g. `2024-07-04`. Defaults
+    /// to the latest archived run.
+    date: Option<String>,
+}
+
+#[get("/history/{spot_id}")]
+async fn get_history(
+    spot_name: web::Path<String>,
+    history: web::Query<History>,
+    units: web::Query<Units>,
+    spots: web::Data<Spots>,
+    archive: web::Data<Archive>,
+    config: web::Data<Config>,
+    render: RenderChoice,
+) -> Result<HttpResponse> {
+    let spot = find_spot(&spots, &config, &spot_name)?;
+    let run = match &history.date {
+        Some(date) => {
+            let date = date
+                .parse()
+                .map_err(|_| ErrorBadRequest("date must be formatted as YYYY-MM-DD"))?;
+            archive.as_of(&spot.slug, date)
+        }
+        None => archive
+            .latest(&spot.slug, 1)
+            .map(|runs| runs.into_iter().next()),
+    }
+    .map_err(|e| ErrorInternalServerError(e.to_string()))?
+    .ok_or_else(|| ErrorNotFound("no archived forecast for that date"))?;
+    Ok(spot_response(spot, units.or(&config).apply(run.forecast), render).await)
+}
+
+#[get("/{spot_id}/diff")]
+async fn get_diff(
+    spot_name: web::Path<String>,
+    units: web::Query<Units>,
+    spots: web::Data<Spots>,
+    cache: web::Data<ForecastCache>,
+    archive: web::Data<Archive>,
+    config: web::Data<Config>,
+    render: RenderChoice,
+) -> Result<HttpResponse> {
+    let spot = find_spot(&spots, &config, &spot_name)?;
+    let units = units.or(&config);
+    // Fetching archives the latest run. If upstream is down, compare the runs
+    // we already have.
+    let _ = cache.forecast(&spot, None).await;
+    let (after, before) = archive
+        .latest_pair(&spot.slug)
+        .map_err(|e| ErrorInternalServerError(e.to_string()))?
+        .ok_or_else(|| ErrorNotFound("no newer model run to compare against"))?;
+    let convert = |run: Run| Run {
+        forecast: units.apply(run.forecast),
+        ..run
+    };
+    let diff = RunDiff::new(&convert(before), &convert(after));
+    Ok(match render.format {
+        Format::Json => json_response(api::diff(spot, diff)),
+        _ => render.into_response(diff).await,
+    })
+}
+
+/// Most spots to compare at once, to bound the upstream requests per request
+const MAX_COMPARE_SPOTS: usize = 5;
+
+/// Query params for the compare endpoint
+#[derive(Clone, Debug, Deserialize)]
+struct Compare {
+    /// Comma separated spot names, e.g. `ormond-beach,ormond-beach-pier`
+    spots: String,
+}
+
+#[get("/compare")]
+async fn compare(
+    compare: web::Query<Compare>,
+    units: web::Query<Units>,
+    spots: web::Data<Spots>,
+    cache: web::Data<ForecastCache>,
+    config: web::Data<Config>,
+    render: RenderChoice,
+) -> Result<HttpResponse> {
+    let names = compare
+        .spots
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<_>>();
+    if !(2..=MAX_COMPARE_SPOTS).contains(&names.len()) {
+        return Err(ErrorBadRequest(format!(
+            "spots must list 2 to {MAX_COMPARE_SPOTS} comma separated spot names"
+        )));
+    }
+    let units = units.or(&config).units;
+    let spots = names
+        .into_iter()
+        .map(|name| find_spot(&spots, &config, name))
+        .collect::<Result<Vec<_>>>()?;
+    let fetched = join_all(spots.iter().map(|spot| cache.forecast(spot, units))).await;
+    let mut forecasts = Vec::with_capacity(spots.len());
+    let mut errors = Vec::new();
+    for (spot, forecast) in spots.into_iter().zip(fetched) {
+        match forecast {
+            Ok(forecast) => forecasts.push((spot, forecast)),
+            Err(e) => errors.push((spot.slug, e)),
+        }
+    }
+    if forecasts.is_empty() {
+        let (slug, e) = &errors[0];
+        return Err(ErrorInternalServerError(format!(
+            "couldn't forecast any spot, e.g. {slug}: {e:#}"
+        )));
+    }
+    Ok(match render.format {
+        Format::Json => json_response(api::compare(forecasts)),
+        _ => {
+            let comparison = forecasts
+                .into_iter()
+                .fold(ui::Comparison::new(), |view, (spot, forecast)| {
+                    view.spot(spot, forecast)
+                });
+            let comparison = errors.into_iter().fold(comparison, |view, (slug, e)| {
+                view.error(slug, format!("{e:#}"))
+            });
+            render.into_response(comparison).await
+        }
+    })
+}
+
+/// Look up a spot by name, or by the alias of a configured favorite
+fn find_spot(spots: &Spots, config: &Config, name: &str) -> Result<Spot> {
+    spots
+        .find(config.resolve(name))
+        .ok_or_else(|| ErrorNotFound("spot name not found"))
+}
+
+async fn spot_response(spot: Spot, forecast: Vec<Forecast>, render: RenderChoice) -> HttpResponse {
+    match render.format {
+        Format::Json => {
+            let rater = Rater::for_spot(&spot).range(&forecast);
+            json_response(api::forecast(Some(spot), forecast, Some(&rater)))
+        }
+        _ => {
+            render
+                .into_response(ui::Report::for_spot(forecast, &spot))
+                .await
+        }
+    }
+}
+
+#[get("/buoy/{station}")]
+async fn get_buoy(station: web::Path<String>, render: RenderChoice) -> Result<HttpResponse> {
+    if !ndbc::is_station_id(&station) {
+        return Err(ErrorBadRequest("buoy station must be letters and digits"));
+    }
+    let buoy = BuoyAPI::new()
+        .get(station.as_str())
+        .await
+        .map_err(|e| ErrorInternalServerError(e.to_string()))?
+        .ok_or_else(|| ErrorNotFound("buoy station not found"))?;
+    Ok(match render.format {
+        Format::Json => json_response(api::buoy(buoy)),
+        _ => render.into_response(buoy).await,
+    })
+}
+
+#[get("/spots")]
+async fn list_spots(
+    spots: web::Data<Spots>,
+    search: web::Query<HashMap<String, String>>,
+    render: RenderChoice,
+) -> impl Responder {
+    let search = search
+        .keys()
+        .find(|key| ![FORMAT_PARAM, WIDTH_PARAM, THEME_PARAM, ASCII_PARAM].contains(&key.as_str()))
+        .map(String::as_str);
+    let found = spots.search(search);
+    if let Format::Json = render.format {
+        return json_response(api::spots(found));
+    }
+    let spot_list = found
+        .into_iter()
+        .map(|spot| (spot.slug, spot.id))
+        .collect::<Vec<_>>();
+    render.into_response(spot_list).await
+}
+
+/// Query param to request a particular format, i.e. `?format=json`
+const FORMAT_PARAM: &str = "format";
+/// Query param to lay out views for a particular width in columns, i.e.
+/// `?width=60`
+const WIDTH_PARAM: &str = "width";
+/// Query param to pick a theme, i.e. `?theme=light`
+const THEME_PARAM: &str = "theme";
+/// Query param to print text with ASCII characters only, i.e. `?ascii`
+const ASCII_PARAM: &str = "ascii";
+
+/// How to render the response, at what width, and in which theme
+struct RenderChoice {
+    format: Format,
+    width: usize,
+    theme: ui::Theme,
+}
+
+enum Format {
+    Terminal(ui::ColorDepth),
+    /// Terminal without colors, if configured
+    Plain,
+    /// Plain text in 7-bit ASCII
+    Ascii,
+    Browser,
+    /// Image to embed elsewhere, e.g. in a wiki or chat
+    Svg,
+    /// Image for chat apps that can't show SVG
+    Png,
+    /// Text to paste into issues and chat, e.g. GitHub, Notion and Discord
+    Markdown,
+    Json,
+}
+
+impl actix_web::FromRequest for RenderChoice {
+    type Error = actix_web::Error;
+    type Future = std::future::Ready<Result<Self, Self::Error>>;
+
+    #[inline]
+    fn from_request(req: &actix_web::HttpRequest, _: &mut actix_web::dev::Payload) -> Self::Future {
+        let query = web::Query::<HashMap<String, String>>::from_query(req.query_string()).ok();
+        let param = |name| query.as_ref().and_then(|query| query.get(name));
+        let width = match param(WIDTH_PARAM).map(|width| width.parse()) {
+            Some(Ok(width)) => width,
+            Some(Err(_)) => {
+                return future::ready(Err(ErrorBadRequest("width must be a number of columns")))
+            }
+            None => ui::DEFAULT_WIDTH,
+        };
+        let config = req.app_data::<web::Data<Config>>();
+        let theme = match param(THEME_PARAM).map(|theme| theme.parse::<ui::Theme>()) {
+            Some(Ok(theme)) => theme,
+            Some(Err(e)) => return future::ready(Err(ErrorBadRequest(e.to_string()))),
+            None => config.and_then(|config| config.theme).unwrap_or_default(),
+        };
+
+        let format_param = param(FORMAT_PARAM).map(String::as_str);
+        let requested = match format_param {
+            Some("svg") => Some(Format::Svg),
+            Some("png") => Some(Format::Png),
+            Some("md") => Some(Format::Markdown),
+            _ => None,
+        };
+        if let Some(format) = requested {
+            return future::ready(Ok(RenderChoice {
+                format,
+                width,
+                theme,
+            }));
+        }
+        let json_param = format_param == Some("json");
+        let json_accepted = req
+            .headers()
+            .get(ACCEPT)
+            .and_then(|accept| accept.to_str().ok())
+            .is_some_and(|accept| accept.contains("application/json"));
+        let ascii = param(ASCII_PARAM).is_some();
+        if json_param || json_accepted && !ascii {
+            return future::ready(Ok(RenderChoice {
+                format: Format::Json,
+                width,
+                theme,
+            }));
+        }
+        if ascii {
+            return future::ready(Ok(RenderChoice {
+                format: Format::Ascii,
+                width,
+                theme,
+            }));
+        }
+
+        let color = config.and_then(|config| config.color).unwrap_or(true);
+        let depth = config.and_then(|config| config.colors).unwrap_or_default();
+        let header = req.headers().get(USER_AGENT);
+        let res = from_one_raw_str(header).map(|user_agent: String| {
+            let format = if TERMINAL_USER_AGENTS
+                .iter()
+                .any(|agent| user_agent.contains(agent))
+            {
+                if color {
+                    Format::Terminal(depth)
+                } else {
+                    Format::Plain
+                }
+            } else {
+                Format::Browser
+            };
+            RenderChoice {
+                format,
+                width,
+                theme,
+            }
+        });
+        future::ready(res.map_err(Into::into))
+    }
+}
+
+impl RenderChoice {
+    /// Render the view at the requested width. Endpoints without a JSON
+    /// representation fall back to plain text.
+    async fn into_response(self, view: impl ui::Layout) -> HttpResponse {
+        match self.format {
+            Format::Terminal(depth) => HttpResponse::build(StatusCode::OK).body(ui::render_width(
+                ui::Terminal::new(depth).theme(self.theme),
+                view,
+                self.width,
+            )),
+            Format::Plain | Format::Json => HttpResponse::build(StatusCode::OK)
+                .body(ui::render_width(ui::Plain, view, self.width)),
+            Format::Ascii => HttpResponse::build(StatusCode::OK)
+                .content_type("text/plain; charset=us-ascii")
+                .body(ui::render_width(ui::Ascii, view, self.width)),
+            Format::Browser => HttpResponse::build(StatusCode::OK)
+                .content_type("text/html; charset=utf-8")
+                .body(ui::render_width(
+                    ui::Browser::new().theme(self.theme),
+                    view,
+                    self.width,
+                )),
+            Format::Svg => HttpResponse::build(StatusCode::OK)
+                .content_type("image/svg+xml")
+                .body(ui::render_width(
+                    ui::Svg::new().theme(self.theme),
+                    view,
+                    self.width,
+                )),
+            Format::Markdown => HttpResponse::build(StatusCode::OK)
+                .content_type("text/markdown; charset=utf-8")
+                .body(ui::render_width(ui::Markdown, view, self.width)),
+            Format::Png => {
+                // Rasterizing is CPU bound, so keep it off the async workers
+                let png = ui::Png::new().theme(self.theme);
+                let view = ui::layout_width(&png, view, self.width);
+                match web::block(move || png.render(view)).await {
+                    Ok(Ok(png)) => HttpResponse::build(StatusCode::OK)
+                        .content_type("image/png")
+                        .body(png),
+                    Ok(Err(e)) => HttpResponse::InternalServerError().body(e.to_string()),
+                    Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+                }
+            }
+        }
+    }
+}
+
+fn json_response(body: impl Serialize) -> HttpResponse {
+    HttpResponse::Ok().json(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use actix_web::test as actix_test;
+    use async_trait::async_trait;
+    use lib::provider::{ForecastProvider, Recorded};
+
+    use super::*;
+
+    #[test]
+    fn options_override_config() {
+        let config = Config {
+            provider: Some("msw".to_owned()),
+            spots_path: Some(PathBuf::from("config/spots.json")),
+            ..Config::default()
+        };
+        let server = Server::new()
+            .config(config)
+            .provider("open-meteo")
+            .spots_path("cli/spots.json");
+        assert_eq!(server.provider, "open-meteo");
+        assert_eq!(server.spots_path, PathBuf::from("cli/spots.json"));
+
+        let server = Server::new().provider("msw").config(Config::default());
+        assert_eq!(server.provider, "msw");
+    }
+
+    #[actix_web::test]
+    async fn serves_spot_forecast() {
+        let spots = Spots::from_reader(include_str!("../../test/spots.json").as_bytes()).unwrap();
+        let provider = Recorded::from_json(include_str!("../../test/msw/forecast.json")).unwrap();
+        let app = actix_test::init_service(
+            App::new()
+                .configure(routes)
+                .app_data(web::Data::new(spots))
+                .app_data(web::Data::new(ForecastCache::new(Arc::new(provider))))
+                .app_data(web::Data::new(Config::default())),
+        )
+        .await;
+
+        let req = actix_test::TestRequest::get()
+            .uri("/ormond-beach?ascii")
+            .insert_header((USER_AGENT, "curl/8.0"))
+            .to_request();
+        let body = actix_test::call_and_read_body(&app, req).await;
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("Tue Jul 05"), "{body}");
+
+        let req = actix_test::TestRequest::get()
+            .uri("/ormond-beach?format=json")
+            .to_request();
+        let json: serde_json::Value = actix_test::call_and_read_body_json(&app, req).await;
+        assert_eq!(json["spot"]["slug"], "ormond-beach");
+
+        let req = actix_test::TestRequest::get()
+            .uri("/ormond-beach.png")
+            .insert_header((USER_AGENT, "curl/8.0"))
+            .to_request();
+        let png = actix_test::call_and_read_body(&app, req).await;
+        assert!(png.starts_with(b"\x89PNG"));
+
+        let req = actix_test::TestRequest::get()
+            .uri("/nowhere")
+            .insert_header((USER_AGENT, "curl/8.0"))
+            .to_request();
+        let res = actix_test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
+    /// Replays a recorded forecast, except for spots it fails on
+    struct Flaky {
+        recorded: Recorded,
+        failing: &'static str,
+    }
+
+    #[async_trait]
+    impl ForecastProvider for Flaky {
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        async fn forecast(&self, spot: &Spot) -> anyhow::Result<Vec<Forecast>> {
+            if spot.slug == self.failing {
+                anyhow::bail!("upstream unavailable");
+            }
+            self.recorded.forecast(spot).await
+        }
+    }
+
+    #[actix_web::test]
+    async fn compares_despite_failed_spots() {
+        let spots = Spots::from_reader(include_str!("../../test/spots.json").as_bytes()).unwrap();
+        let provider = Flaky {
+            recorded: Recorded::from_json(include_str!("../../test/msw/forecast.json")).unwrap(),
+            failing: "pipeline",
+        };
+        let app = actix_test::init_service(
+            App::new()
+                .configure(routes)
+                .app_data(web::Data::new(spots))
+                .app_data(web::Data::new(ForecastCache::new(Arc::new(provider))))
+                .app_data(web::Data::new(Config::default())),
+        )
+        .await;
+
+        let req = actix_test::TestRequest::get()
+            .uri("/compare?spots=ormond-beach,pipeline&ascii")
+            .insert_header((USER_AGENT, "curl/8.0"))
+            .to_request();
+        let body = actix_test::call_and_read_body(&app, req).await;
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("upstream unavailable"), "{body}");
+        assert!(body.contains("Tue Jul 05"), "{body}");
+
+        let req = actix_test::TestRequest::get()
+            .uri("/compare?spots=ormond-beach,pipeline&format=json")
+            .to_request();
+        let json: serde_json::Value = actix_test::call_and_read_body_json(&app, req).await;
+        assert_eq!(json["spots"].as_array().unwrap().len(), 1);
+        assert_eq!(json["spots"][0]["spot"]["slug"], "ormond-beach");
+
+        let req = actix_test::TestRequest::get()
+            .uri("/compare?spots=pipeline,pipeline&format=json")
+            .to_request();
+        let res = actix_test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,50 @@
+#YY  MM DD hh mm WVHT  SwH  SwP  WWH  WWP SwD WWD  STEEPNESS  APD MWD
+#yr  mo dy hr mn    m    m  sec    m  sec  -  degT     -      sec degT
+2024 07 03 16 40  1.1  0.9  8.3  0.6  4.5   E SSE    AVERAGE  5.6  95
+2024 07 03 15 40  1.1  0.9  8.3  0.6  4.6   E SSE    AVERAGE  5.7  97
+2024 07 03 14 40  1.2  0.9  8.3  0.6  4.6   E   S    AVERAGE  5.7  99
+2024 07 03 13 40  1.2  1.0  8.3  0.7  4.7   E   S    AVERAGE  5.8 100
+2024 07 03 12 40  1.2  1.0  9.3  0.7  4.7   E   S    AVERAGE  5.8 102
+2024 07 03 11 40  1.2  1.0  9.3  0.7  4.8   E   S    AVERAGE  5.9 103
+2024 07 03 10 40  1.3  1.0  9.3  0.7  4.8   E   S    AVERAGE  5.9 104
+2024 07 03 09 40  1.3  1.0  9.3  0.7  4.8   E   S    AVERAGE  5.9 106
+2024 07 03 08 40  1.3  1.1  9.3  0.7  4.8 ESE SSW      STEEP  6.0 107
+2024 07 03 07 40  1.3  1.1  9.3  0.7  4.8 ESE SSW      STEEP  6.0 108
+2024 07 03 06 40  1.4  1.1  9.3  0.7  4.8 ESE SSW      STEEP  6.0 109
+2024 07 03 05 40  1.4  1.1  9.3  0.8  4.7 ESE SSW      STEEP  6.0 109
+2024 07 03 04 40  1.4  1.1 10.3  0.8  4.7 ESE SSW      STEEP  6.0 110
+2024 07 03 03 40  1.4  1.1 10.3  0.8  4.6 ESE SSW      STEEP  6.0 110
+2024 07 03 02 40  1.4  1.1 10.3  0.8  4.6 ESE SSW      STEEP  6.0 110
+2024 07 03 01 40  1.4  1.1 10.3  0.8  4.5 ESE SSW      STEEP  5.9 110
+2024 07 03 00 40  1.4   MM   MM   MM   MM  MM  MM         MM  5.9 110
+2024 07 02 23 40  1.4  1.1 10.3  0.8  4.4 ESE SSW      STEEP  5.9 109
+2024 07 02 22 40  1.4  1.1 10.3  0.8  4.4 ESE   S      STEEP  5.8 109
+2024 07 02 21 40  1.4  1.1 10.3  0.8  4.3 ESE   S      STEEP  5.8 108
+2024 07 02 20 40  1.4  1.1 10.3  0.8  4.3 ESE   S      STEEP  5.7 107
+2024 07 02 19 40  1.4  1.1 10.3  0.7  4.2   E   S      STEEP  5.6 106
+2024 07 02 18 40  1.3  1.1 10.3  0.7  4.2   E   S      STEEP  5.6 104
+2024 07 02 17 40  1.3  1.1 10.3  0.7  4.2   E   S      STEEP  5.5 103
+2024 07 02 16 40  1.3  1.0 10.3  0.7  4.2   E SSE      STEEP  5.5 102
+2024 07 02 15 40  1.3  1.0 10.3  0.7  4.2   E SSE      STEEP  5.4 100
+2024 07 02 14 40  1.3  1.0 10.3  0.7  4.2   E SSE      STEEP  5.4  98
+2024 07 02 13 40  1.2  1.0 10.3  0.7  4.3   E SSE      STEEP  5.3  97
+2024 07 02 12 40  1.2  1.0 10.3  0.7  4.3   E  SE      STEEP  5.3  95
+2024 07 02 11 40  1.2  0.9 10.3  0.6  4.4   E  SE    AVERAGE  5.3  94
+2024 07 02 10 40  1.1  0.9 10.3  0.6  4.4   E  SE    AVERAGE  5.2  92
+2024 07 02 09 40  1.1  0.9 10.3  0.6  4.5   E  SE    AVERAGE  5.2  90
+2024 07 02 08 40  1.1  0.9  9.3  0.6  4.5   E  SE    AVERAGE  5.2  89
+2024 07 02 07 40  1.0  0.8  9.3  0.6  4.6   E  SE    AVERAGE  5.2  87
+2024 07 02 06 40  1.0  0.8  9.3  0.6  4.7   E  SE    AVERAGE  5.2  86
+2024 07 02 05 40  1.0  0.8  9.3  0.5  4.7   E ESE    AVERAGE  5.2  85
+2024 07 02 04 40  1.0  0.8  9.3  0.5  4.7 ENE ESE    AVERAGE  5.2  83
+2024 07 02 03 40  0.9  0.7  9.3  0.5  4.8 ENE ESE    AVERAGE  5.3  82
+2024 07 02 02 40  0.9  0.7  9.3  0.5  4.8 ENE ESE    AVERAGE  5.3  82
+2024 07 02 01 40  0.9  0.7  9.3  0.5  4.8 ENE ESE    AVERAGE  5.3  81
+2024 07 02 00 40  0.9  0.7  9.3  0.5  4.8 ENE ESE    AVERAGE  5.4  80
+2024 07 01 23 40  0.9  0.7  8.3  0.5  4.8 ENE  SE    AVERAGE  5.4  80
+2024 07 01 22 40  0.8  0.7  8.3  0.5  4.8 ENE  SE    AVERAGE  5.5  80
+2024 07 01 21 40  0.8  0.7  8.3  0.5  4.7 ENE  SE    AVERAGE  5.6  80
+2024 07 01 20 40  0.8  0.7  8.3  0.4  4.7 ENE  SE    AVERAGE  5.6  80
+2024 07 01 19 40  0.8  0.6  8.3  0.4  4.6 ENE  SE    AVERAGE  5.7  81
+2024 07 01 18 40  0.8  0.6  8.3  0.4  4.6 ENE  SE    AVERAGE  5.7  81
+2024 07 01 17 40  0.8  0.6  8.3  0.4  4.5 ENE  SE    AVERAGE  5.8  82